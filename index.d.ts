@@ -24,8 +24,10 @@ export class Engine extends ExposedObject {
 
     /**
      * Create a dummy engine, for testing purposes.
+     *
+     * If `input` is `true`, the engine has a dummy input device that records silence, so it can record.
      */
-    static getDummy(): Engine;
+    static getDummy(options?: { input?: boolean } | null): Engine;
 
     /**
      * Create and initialize a new engine with the given configuration.
@@ -57,6 +59,26 @@ export class Engine extends ExposedObject {
      * This can be done both while the timeline is playing and while it is paused.
      */
     jumpTo(position: Timestamp): void;
+    /**
+     * Release the audio devices, without closing the engine.
+     * This saves power, and lets other applications take exclusive access to the devices.
+     *
+     * All tracks, clips and stored clips stay usable, but nothing is played or recorded, and the playhead does not move.
+     * Calling this while already suspended does nothing.
+     */
+    suspend(): void;
+    /**
+     * Reopen the audio devices after {@linkcode Engine.suspend()}, using the current config.
+     * Calling this while not suspended does nothing.
+     *
+     * Throws an {@linkcode Error} if the devices cannot be opened, in which case the engine stays suspended.
+     */
+    resume(): void;
+    isSuspended(): boolean;
+    /**
+     * Get whether the audio thread was granted the real-time priority requested in {@linkcode config.ThreadConfig}.
+     */
+    getRealtimePriority(): config.RealtimePriority;
     /**
      * Get the current playhead position.
      *
@@ -64,6 +86,56 @@ export class Engine extends ExposedObject {
      */
     getPlayheadPosition(): Timestamp;
 
+    /**
+     * Get the range that {@linkcode Engine.record()} is limited to, if any.
+     */
+    getPunch(): TimeRange | null;
+    /**
+     * Limit recording to the range from `punchIn` to `punchOut`.
+     * Playback continues outside of the range, but nothing is recorded there.
+     *
+     * If `punchIn` and `punchOut` are equal, the range is cleared like {@linkcode Engine.clearPunch()}.
+     * Throws a {@linkcode RangeError} if `punchOut` comes before `punchIn`.
+     */
+    setPunch(punchIn: Timestamp, punchOut: Timestamp): void;
+    /**
+     * Record for as long as the engine is recording, regardless of position.
+     */
+    clearPunch(): void;
+    /**
+     * Get the range that playback loops over, if any.
+     */
+    getLoop(): TimeRange | null;
+    /**
+     * Loop playback over the range from `start` to `end`, jumping back to `start` whenever the playhead reaches `end`.
+     *
+     * If recording while looping, every pass becomes a separate take of the recorded clip (see {@linkcode AudioClip.getTakes()}).
+     *
+     * If `start` and `end` are equal, the range is cleared like {@linkcode Engine.clearLoop()}.
+     * Throws a {@linkcode RangeError} if `end` comes before `start`.
+     */
+    setLoop(start: Timestamp, end: Timestamp): void;
+    clearLoop(): void;
+
+    /**
+     * Convert `ts` to a number of samples, using the current sample rate of the output device and the current tempo.
+     */
+    toSamples(ts: Timestamp): number;
+    /**
+     * Convert a number of samples to a timestamp, using the current sample rate of the output device and the current tempo.
+     * `samples` parameter is truncated to an integer, and must be representable by a 64-bit unsigned integer.
+     */
+    fromSamples(samples: number): Timestamp;
+    /**
+     * Convert `ts` to seconds, using the current tempo.
+     */
+    toSeconds(ts: Timestamp): number;
+    /**
+     * Convert seconds to a timestamp, using the current tempo.
+     * Parameter is rounded to the nearest beat unit, which must be representable by a 32-bit unsigned integer.
+     */
+    fromSeconds(seconds: number): Timestamp;
+
     /**
      * Get the master track, which is always present on the mixer.
      */
@@ -115,11 +187,216 @@ export class Engine extends ExposedObject {
      */
     reconstructAudioTracks(states: AudioTrackState[]): AudioTrack[];
 
+    /**
+     * Get all clips that overlap the range from `start` to `end` on the timeline.
+     *
+     * A clip overlaps if any part of it lies within the range, where `start` is included and `end` is not.
+     * If `start` and `end` are equal, the clips that contain that single position are returned.
+     * Throws a {@linkcode RangeError} if `end` comes before `start`.
+     *
+     * @param options.tracks    The audio tracks to search. If not set, all audio tracks are searched.
+     */
+    getClipsInRange(
+        start: Timestamp,
+        end: Timestamp,
+        options?: { tracks?: AudioTrack[] | null } | null,
+    ): AudioClip[];
+
+    /**
+     * Insert empty time on the timeline.
+     *
+     * Every clip after `at` is moved later by `length`, and clips that straddle `at` are split in two.
+     * Nothing is changed if `length` is zero, or if the edit fails on any of the tracks.
+     *
+     * Returns a state that can be passed to {@linkcode Engine.revertTimelineEdit()} to undo the edit.
+     *
+     * @param options.tracks    The audio tracks to edit. If not set, all audio tracks are edited.
+     */
+    insertTime(
+        at: Timestamp,
+        length: Timestamp,
+        options?: { tracks?: AudioTrack[] | null } | null,
+    ): TimelineEditState;
+    /**
+     * Remove the time from `start` to `end` on the timeline.
+     *
+     * Clips within the range are deleted, clips that straddle either end of it are cropped,
+     * and every clip after it is moved earlier to close the gap.
+     * Nothing is changed if `start` and `end` are equal, or if the edit fails on any of the tracks.
+     * Throws a {@linkcode RangeError} if `end` comes before `start`.
+     *
+     * Returns a state that can be passed to {@linkcode Engine.revertTimelineEdit()} to undo the edit.
+     *
+     * @param options.tracks    The audio tracks to edit. If not set, all audio tracks are edited.
+     */
+    removeTime(
+        start: Timestamp,
+        end: Timestamp,
+        options?: { tracks?: AudioTrack[] | null } | null,
+    ): TimelineEditState;
+    /**
+     * Copy the range from `start` to `end` on the timeline.
+     *
+     * Clips that straddle either end of the range are cropped to it in the copy, while the originals are left untouched.
+     * The result can be serialized with `JSON.stringify()` and passed to {@linkcode Engine.paste()}.
+     * If `start` and `end` are equal, the copy is empty.
+     * Throws a {@linkcode RangeError} if `end` comes before `start`.
+     *
+     * @param options.tracks    The audio tracks to copy. If not set, all audio tracks are copied.
+     */
+    copyTime(
+        start: Timestamp,
+        end: Timestamp,
+        options?: { tracks?: AudioTrack[] | null } | null,
+    ): ClipboardData;
+    /**
+     * Copy the range from `start` to `end` on the timeline like {@linkcode Engine.copyTime()}, and then clear it.
+     *
+     * Clips within the range are deleted and clips that straddle either end of it are cropped,
+     * while clips after it stay in place.
+     *
+     * Returns the copied range, along with a state that can be passed to {@linkcode Engine.revertTimelineEdit()} to undo the cut.
+     * Throws a {@linkcode RangeError} if `end` comes before `start`.
+     *
+     * @param options.tracks    The audio tracks to cut. If not set, all audio tracks are cut.
+     */
+    cutTime(
+        start: Timestamp,
+        end: Timestamp,
+        options?: { tracks?: AudioTrack[] | null } | null,
+    ): { clipboard: ClipboardData; state: TimelineEditState };
+    /**
+     * Paste a range copied by {@linkcode Engine.copyTime()} or {@linkcode Engine.cutTime()}, so it starts at `at`.
+     *
+     * The clips of the n'th copied track are placed on the n'th track in `tracks`,
+     * which must hold the same number of tracks as the clipboard.
+     * Existing clips in the way are handled according to `overlap`.
+     * If any clip can't be pasted, e.g. because it would overlap in {@linkcode OverlapMode.Reject}, nothing is pasted or changed.
+     *
+     * Returns the pasted clips.
+     *
+     * @param overlap   Defaults to {@linkcode OverlapMode.Reject}.
+     */
+    paste(
+        clipboard: ClipboardData,
+        at: Timestamp,
+        tracks: AudioTrack[],
+        overlap?: OverlapMode,
+    ): AudioClip[];
+
+    /**
+     * Undo an edit made by {@linkcode Engine.insertTime()}, {@linkcode Engine.removeTime()}, {@linkcode Engine.cutTime()}
+     * or {@linkcode AudioClip.split()}.
+     *
+     * The clips resulting from the edit are deleted, even if they were moved since, and the original clips are placed back.
+     * Clips that were split by the edit are merged again, and keep their original keys.
+     */
+    revertTimelineEdit(state: TimelineEditState): void;
+
     /**
      * Import audio clip from file.
      */
     importAudioClip(path: string): StoredAudioClip;
 
+    /**
+     * Start playing and recording from the current playhead position onto every record-armed {@linkcode AudioTrack}.
+     *
+     * The incoming audio is written to files in `directory`.
+     *
+     * Throws an {@linkcode Error} if the engine has no input device (see {@linkcode config.Config}).
+     */
+    record(directory: string): void;
+    /**
+     * Whether the engine is currently recording.
+     */
+    isRecording(): boolean;
+    /**
+     * Get the current latency of the engine in samples, at the sample rate of the output device.
+     */
+    getLatency(): Latency;
+
+    /**
+     * Get performance statistics of the audio thread, since the engine was started.
+     */
+    getStats(): Stats;
+    /**
+     * Call `callback` whenever buffer underruns or overruns (xruns) have happened, which are heard as crackling.
+     *
+     * The stats are checked every `interval` milliseconds, defaulting to 1000,
+     * so multiple xruns might be reported in a single event.
+     * Checking stops when the watcher is stopped or the engine is closed.
+     * The watcher does not keep the process alive on its own.
+     *
+     * Throws a {@linkcode RangeError} if `interval` is not a positive number.
+     */
+    onXrun(
+        callback: (event: XrunEvent) => void,
+        options?: { interval?: number | null } | null,
+    ): config.DeviceWatcher;
+    /**
+     * Stop recording, and add the recorded audio to the timeline.
+     *
+     * Each record-armed track gets a new {@linkcode AudioClip} at the position where recording started,
+     * or at the punch-in point if one is set (see {@linkcode Engine.setPunch()}),
+     * whose {@linkcode StoredAudioClip} is the recorded file.
+     * When loop recording, each pass is a take of the clip, and the last pass is the active one.
+     *
+     * The clips are compensated for the total latency reported by {@linkcode Engine.getLatency()},
+     * by skipping that much at the start of the recording, so they line up with what was heard while recording.
+     * Any clips in the way are handled like {@linkcode OverlapMode.Replace}.
+     *
+     * Each recording is added on its own, so if one can't be added, the others still are,
+     * and the clips in its way are left as they were.
+     *
+     * Returns the new clips, the states of the clips or parts of clips that were deleted to make room for them,
+     * which can be passed to {@linkcode AudioTrack.reconstructClips()} after the new clips have been deleted again,
+     * and the errors of any recordings that could not be added.
+     */
+    stopRecording(): {
+        clips: AudioClip[];
+        removed: AudioClipState[];
+        errors: Error[];
+    };
+
+    /**
+     * Call `callback` with the name of the engine's output device, when that device disappears.
+     *
+     * The device is checked every `interval` milliseconds, defaulting to 1000.
+     * Checking stops when the watcher is stopped or the engine is closed.
+     * The watcher does not keep the process alive on its own.
+     *
+     * Throws a {@linkcode RangeError} if `interval` is not a positive number.
+     */
+    onOutputDeviceLost(
+        callback: (outputDevice: string) => void,
+        options?: { interval?: number | null } | null,
+    ): config.DeviceWatcher;
+
+    /**
+     * Switch to `target` automatically when the engine's output device disappears,
+     * and call `callback` after each switch, or after each failed attempt to switch.
+     * A device that stays gone is only switched away from once.
+     *
+     * The tracks, clips and mixer state are kept,
+     * and so are the playhead position and whether the engine is playing.
+     *
+     * The device is checked every `interval` milliseconds, defaulting to 1000.
+     * Checking stops when the watcher is stopped or the engine is closed.
+     * The watcher does not keep the process alive on its own.
+     * An engine has only one failover watcher, so calling this again stops the previous one.
+     *
+     * If the engine can't be locked to switch, because a panic occurred while it was locked,
+     * `callback` is still called, with `error` saying so.
+     *
+     * Throws an {@linkcode Error} if the engine has been closed.
+     * Throws a {@linkcode RangeError} if `interval` is not a positive number.
+     */
+    enableFailover(
+        target: FailoverTarget,
+        callback: (event: FailoverEvent) => void,
+        options?: { interval?: number | null } | null,
+    ): config.DeviceWatcher;
+
     /**
      * Closes down the engine gracefully.
      * After this is called all other functions and methods (not just on {@linkcode Engine}) may throw an {@linkcode Error}.
@@ -191,17 +468,35 @@ export class AudioTrack extends Track {
     getClips(): AudioClip[];
 
     /**
-     * Add clip to track.
+     * Add clip to track, handling any existing clips in the way according to `overlap`.
+     *
+     * By default, throws an {@linkcode OverlapError} if the clip would overlap any existing clips.
+     * If the clip can't be added, the existing clips are left as they were.
+     * To undo {@linkcode OverlapMode.Replace}, use {@linkcode AudioTrack.placeClip()} instead,
+     * which also returns the states of the clips that were deleted.
      *
      * @param clip      The stored clip, as returned by {@linkcode Engine.importAudioClip()}.
      * @param start     The start position of the clip.
      * @param length    The length of the clip. If not set, the entire clip is used.
+     * @param overlap   How to handle existing clips in the way. Defaults to {@linkcode OverlapMode.Reject}.
      */
     addClip(
         clip: StoredAudioClip,
         start: Timestamp,
-        length?: Timestamp,
+        length?: Timestamp | null,
+        overlap?: OverlapMode,
     ): AudioClip;
+    /**
+     * Same as {@linkcode AudioTrack.addClip()}, but also returns the states of any clips or parts of clips
+     * that were deleted to make room for the new clip in {@linkcode OverlapMode.Replace}.
+     * These can be passed to {@linkcode AudioTrack.reconstructClips()} after the new clip has been deleted again.
+     */
+    placeClip(
+        clip: StoredAudioClip,
+        start: Timestamp,
+        length?: Timestamp | null,
+        overlap?: OverlapMode,
+    ): { clip: AudioClip; removed: AudioClipState[] };
 
     /**
      * Delete clip from track.
@@ -227,6 +522,63 @@ export class AudioTrack extends Track {
      */
     reconstructClips(states: AudioClipState[]): AudioClip[];
 
+    /**
+     * Crossfade between two clips on this track, where `left` ends where `right` starts.
+     *
+     * `left` is extended past its end by `length`, so it overlaps the start of `right`, which stays in place.
+     * Over this region `left` fades out and `right` fades in, as reported by {@linkcode AudioClip.getFadeOut()} and {@linkcode AudioClip.getFadeIn()}.
+     *
+     * Calling this again on the same clips adjusts the crossfade, and a length of zero removes it.
+     *
+     * @param curve     The curve of the fades. Defaults to {@linkcode FadeCurve.EqualPower}.
+     */
+    crossfade(
+        left: AudioClip,
+        right: AudioClip,
+        length: Timestamp,
+        curve?: FadeCurve,
+    ): void;
+
+    /**
+     * Create a new audio track with the same volume, panning, input channels and monitor mode as this one,
+     * and a duplicate of every clip on its timeline (see {@linkcode AudioClip.duplicate()}).
+     * The new track is not record-armed, even if this one is.
+     *
+     * If any clip cannot be duplicated, the new track is deleted again before the error is thrown.
+     */
+    duplicate(): AudioTrack;
+
+    /**
+     * Get every clip on this track that has more than one take, for choosing between them.
+     * See {@linkcode AudioClip.getTakes()}.
+     */
+    getTakeRegions(): TakeRegion[];
+
+    /**
+     * Whether live input is played through this track's mixer chain.
+     * Defaults to {@linkcode MonitorMode.Off}.
+     */
+    getMonitorMode(): MonitorMode;
+    setMonitorMode(mode: MonitorMode): void;
+
+    /**
+     * Whether this track is recorded onto by {@linkcode Engine.record()}. Tracks are not armed by default.
+     */
+    isRecordArmed(): boolean;
+    setRecordArmed(armed: boolean): void;
+    /**
+     * Get the channels of the input device that this track records from, starting from 0.
+     */
+    getInputChannels(): number[];
+    /**
+     * Set the channels of the input device that this track records from, starting from 0.
+     * Give one channel to record in mono, or two to record in stereo.
+     *
+     * Throws a {@linkcode RangeError} if `channels` is empty,
+     * or if any of them does not exist on the input device of the engine.
+     */
+    setInputChannels(channels: number[]): void;
+
     /**
      * Alias for {@linkcode Engine.deleteAudioTrack()|Engine.deleteAudioTrack(this)}:
      *
@@ -297,14 +649,33 @@ export abstract class Clip extends ExposedObject {
     getLength(): Timestamp;
 
     /**
-     * Set the start position of the clip.
+     * Set the start position of the clip, handling any other clips in the way according to `overlap`.
+     *
+     * Returns the states of any clips or parts of clips that were deleted to make room.
+     * If the clip can't be moved, it and the other clips are left as they were.
+     */
+    move(newStart: Timestamp, overlap?: OverlapMode): ClipState[];
+    /**
+     * Move the clip by `delta` relative to its current start position, handling any other clips in the way according to `overlap`.
+     *
+     * Throws a {@linkcode RangeError} if this would move the clip before the beginning or past the end of the timeline.
+     *
+     * Returns the states of any clips or parts of clips that were deleted to make room.
+     * If the clip can't be moved, it and the other clips are left as they were.
      */
-    move(newStart: Timestamp): void;
+    nudge(delta: Offset, overlap?: OverlapMode): ClipState[];
 
     /**
-     * Move clip to the given position on another track.
+     * Move clip to the given position on another track, handling any clips in the way according to `overlap`.
+     *
+     * Returns the states of any clips or parts of clips that were deleted to make room.
+     * If the clip can't be moved, it and the other clips are left as they were.
      */
-    moveToTrack(newStart: Timestamp, newTrack: Track): void;
+    moveToTrack(
+        newStart: Timestamp,
+        newTrack: Track,
+        overlap?: OverlapMode,
+    ): ClipState[];
 
     /**
      * Set the length of the clip, keeping the end position fixed.
@@ -321,11 +692,34 @@ export abstract class Clip extends ExposedObject {
      */
     cropEnd(newLength: Timestamp): void;
 
+    /**
+     * Get the track that the clip is currently placed on.
+     */
+    getTrack(): Track;
+
     /**
      * Get the referenced stored clip.
      */
     getStoredClip(): StoredClip;
 
+    /**
+     * Get the position in the stored clip that playback of this clip starts from.
+     *
+     * This is changed by {@linkcode cropStart()} and {@linkcode slip()}.
+     */
+    getSourceOffset(): Timestamp;
+
+    /**
+     * Move the audio inside the clip by `delta`, while the clip itself stays in place on the timeline.
+     *
+     * A positive `delta` moves the audio forwards (later) on the timeline, i.e. the source offset is decreased.
+     * A negative `delta` moves the audio backwards (earlier), i.e. the source offset is increased.
+     *
+     * Throws a {@linkcode RangeError} if this would move the start of the clip before the start of the stored clip,
+     * or the end of the clip past the end of the stored clip.
+     */
+    slip(delta: Offset): void;
+
     /**
      * Delete clip from track.
      * After this is done, calling any method on the clip will throw an {@linkcode Error}.
@@ -342,15 +736,121 @@ export class AudioClip extends Clip {
     private constructor();
 
     /**
-     * Move clip to the given position on another track.
+     * Set the start position of the clip, handling any other clips in the way according to `overlap`.
+     *
+     * Returns the states of any clips or parts of clips that were deleted to make room.
+     * These can be passed to {@linkcode AudioTrack.reconstructClips()}.
+     */
+    override move(newStart: Timestamp, overlap?: OverlapMode): AudioClipState[];
+    /**
+     * Move the clip by `delta` relative to its current start position, handling any other clips in the way according to `overlap`.
+     *
+     * Throws a {@linkcode RangeError} if this would move the clip before the beginning or past the end of the timeline.
+     *
+     * Returns the states of any clips or parts of clips that were deleted to make room.
+     * These can be passed to {@linkcode AudioTrack.reconstructClips()}.
+     */
+    override nudge(delta: Offset, overlap?: OverlapMode): AudioClipState[];
+
+    /**
+     * Move clip to the given position on another track, handling any clips in the way according to `overlap`.
+     *
+     * Returns the states of any clips or parts of clips that were deleted to make room.
+     * These can be passed to {@linkcode AudioTrack.reconstructClips()}.
+     */
+    override moveToTrack(
+        newStart: Timestamp,
+        newTrack: AudioTrack,
+        overlap?: OverlapMode,
+    ): AudioClipState[];
+
+    /**
+     * Get the audio track that the clip is currently placed on.
+     *
+     * This changes when the clip is moved with {@linkcode moveToTrack()}.
      */
-    override moveToTrack(newStart: Timestamp, newTrack: AudioTrack): void;
+    override getTrack(): AudioTrack;
 
     /**
      * Get the clip in the store that this clip references.
      */
     override getStoredClip(): StoredAudioClip;
 
+    /**
+     * Get every take of the clip in the order they were recorded, one for each pass of a loop recording.
+     *
+     * A clip that was not loop recorded has a single take, which is its stored clip.
+     */
+    getTakes(): StoredAudioClip[];
+    /**
+     * Get the index in {@linkcode AudioClip.getTakes()} of the take that is played, which is also returned by {@linkcode AudioClip.getStoredClip()}.
+     */
+    getActiveTake(): number;
+    /**
+     * Play the take at `index` in {@linkcode AudioClip.getTakes()} instead.
+     * The clip keeps its position, length and source offset.
+     *
+     * Throws a {@linkcode RangeError} if there is no take at `index`.
+     */
+    setActiveTake(index: number): void;
+
+    /**
+     * Split the clip in two at the given position on the timeline, which must lie strictly within the clip.
+     *
+     * The left part keeps the key of this clip, along with its gain and fade-in,
+     * while the right part gets a new key, the same gain and the fade-out.
+     * The source offset of the right part is adjusted, so the audio stays in place.
+     *
+     * To undo the split, pass the returned state to {@linkcode Engine.revertTimelineEdit()}.
+     */
+    split(at: Timestamp): {
+        left: AudioClip;
+        right: AudioClip;
+        state: TimelineEditState;
+    };
+
+    /**
+     * Create a copy of this clip at the given position, either on the same track or on `targetTrack`.
+     *
+     * The copy references the same {@linkcode StoredAudioClip}, and has the same length, source offset, gain and fades.
+     * Throws an {@linkcode Error} if the copy would overlap another clip.
+     */
+    duplicate(newStart: Timestamp, targetTrack?: AudioTrack): AudioClip;
+
+    /**
+     * Get the gain of the clip, where 0 is muted and 1 leaves the audio unchanged.
+     */
+    getGain(): number;
+    /**
+     * Set the gain of the clip, where 0 is muted and 1 leaves the audio unchanged.
+     *
+     * This is applied before the clip is mixed into its track.
+     */
+    setGain(gain: number): void;
+
+    /**
+     * Get the fade-in at the start of the clip.
+     */
+    getFadeIn(): Fade;
+    /**
+     * Set the length and curve of the fade-in at the start of the clip.
+     *
+     * A length of zero disables the fade-in.
+     * Throws an {@linkcode Error} if the fade-in and fade-out would together be longer than the clip.
+     */
+    setFadeIn(length: Timestamp, curve?: FadeCurve): void;
+    /**
+     * Get the fade-out at the end of the clip.
+     */
+    getFadeOut(): Fade;
+    /**
+     * Set the length and curve of the fade-out at the end of the clip.
+     *
+     * A length of zero disables the fade-out.
+     * Throws an {@linkcode Error} if the fade-in and fade-out would together be longer than the clip.
+     */
+    setFadeOut(length: Timestamp, curve?: FadeCurve): void;
+
     /**
      * Get the data needed to visualize the waveform of the clip.
      *
@@ -360,6 +860,7 @@ export class AudioClip extends Clip {
      * ```
      *
      * The data will be normalized to fit within the range of a 16-bit signed integer, such that the highest peak in the clip will be at 32767 or -32768.
+     * Gain, fades and crossfades are applied before normalization.
      */
     getWaveform(): Int16Array;
 
@@ -373,71 +874,403 @@ export class AudioClip extends Clip {
     override delete(): AudioClipState;
 }
 
-export abstract class ClipState extends ExposedObject {}
-export class AudioClipState extends ClipState {
-    #brand: "AudioClipState";
-    private constructor();
+/**
+ * A fade applied to the start or end of a clip.
+ */
+export interface Fade {
+    /**
+     * The length of the fade. Zero means no fade.
+     */
+    length: Timestamp;
+    /**
+     * The shape of the fade.
+     */
+    curve: FadeCurve;
 }
 
 /**
- * A timestamp is either a point in time or a duration on the timeline,
- * which can be represented in different units.
+ * All possible shapes of a fade.
  */
-export class Timestamp extends ExposedObject {
-    #brand: "Timestamp";
-    private constructor();
-
+export enum FadeCurve {
     /**
-     * Return the smallest of the two timestamps.
+     * The gain changes linearly over the length of the fade.
      */
-    static min(a: Timestamp, b: Timestamp): Timestamp;
+    Linear = "linear",
     /**
-     * Return the largest of the two timestamps.
+     * The power is kept constant over the length of the fade, which avoids a dip in loudness when crossfading.
      */
-    static max(a: Timestamp, b: Timestamp): Timestamp;
+    EqualPower = "equalPower",
     /**
-     * Check whether the two timestamps are equal to each other .
+     * The gain changes exponentially, which is perceived as a steady change in loudness.
      */
-    static eq(a: Timestamp, b: Timestamp): boolean;
+    Exponential = "exponential",
+}
 
+/**
+ * How to handle existing clips when a clip is added or moved on top of them.
+ */
+export enum OverlapMode {
     /**
-     * Add `a` to `b`.
+     * Throw an {@linkcode OverlapError} naming the clips in the way. This is the default.
      */
-    static add(a: Timestamp, b: Timestamp): Timestamp;
+    Reject = "reject",
     /**
-     * Subtract `b` from `a`.
-     * Throws {@linkcode RangeError} if result is less than zero.
+     * Crop the clips in the way so they end where the new clip starts, or start where it ends, without deleting anything.
+     * A clip that sticks out at both ends is split where the new clip ends, and the part before that is cropped.
+     *
+     * Clips that would be entirely covered can't be cropped, so an {@linkcode OverlapError} naming them is thrown instead.
      */
-    static sub(a: Timestamp, b: Timestamp): Timestamp;
+    Trim = "trim",
     /**
-     * Multiplies timestamp `ts` with scalar `s`.
-     * `s` will be truncated to an integer, and a
-     * {@linkcode RangeError} will be thrown if `s` is less than zero.
+     * Delete the parts of the clips in the way that are covered, splitting them if necessary.
      */
-    static mul(ts: Timestamp, s: number): Timestamp;
+    Replace = "replace",
+    /**
+     * Trim the clips in the way like {@linkcode OverlapMode.Trim}, and crossfade with the neighbouring clips it overlapped,
+     * using {@linkcode FadeCurve.EqualPower}.
+     *
+     * The crossfades keep the clips in place and span the region where they overlapped,
+     * but are capped to half the length of the added or moved clip.
+     * See {@linkcode AudioTrack.crossfade()}.
+     */
+    Crossfade = "crossfade",
+}
 
+/**
+ * What an {@linkcode Engine} switches to when its output device disappears.
+ * See {@linkcode Engine.enableFailover()}.
+ */
+export enum FailoverTarget {
     /**
-     * The smallest possible timestamp representing the very beginning (regardless of unit).
+     * The default output device of the default host, using its default config.
+     * If there is no default output device, {@linkcode FailoverTarget.Dummy} is used instead.
      */
-    static zero(): Timestamp;
+    DefaultOutputDevice = "defaultOutputDevice",
     /**
-     * The largest representable timestamp, convenient for comparisons.
-     * Converting this to anything other than beat units may overflow.
+     * A sink that discards all output, but keeps the engine running.
      */
-    static infinity(): Timestamp;
+    Dummy = "dummy",
+}
 
+/**
+ * Reported by {@linkcode Engine.enableFailover()} after switching output device.
+ */
+export interface FailoverEvent {
     /**
-     * Create timestamp from beat units.
-     * Parameter is truncated to an integer, and must be representable by a 32-bit unsigned integer.
-     *
-     * 1 beat = 1024 beat units
+     * The name of the output device that disappeared.
      */
-    static fromBeatUnits(beatUnits: number): Timestamp;
+    lostOutputDevice: string;
     /**
-     * Create timestamp from beats.
+     * The target that was switched to.
+     * This is {@linkcode FailoverTarget.Dummy} if {@linkcode FailoverTarget.DefaultOutputDevice} was requested but unavailable.
+     * If no target could be switched to, this is `null`, and `error` says why.
+     */
+    target: FailoverTarget | null;
+    /**
+     * Why switching failed, or `null` if it succeeded.
+     */
+    error: string | null;
+    /**
+     * The config that the engine now uses, or `null` if the engine could not be locked to switch.
+     */
+    config: config.Config | null;
+}
+
+/**
+ * Thrown when a clip would overlap existing clips and {@linkcode OverlapMode.Reject} is used,
+ * or when it would entirely cover existing clips and {@linkcode OverlapMode.Trim} or {@linkcode OverlapMode.Crossfade} is used.
+ *
+ * This is a plain {@linkcode Error} with extra properties, not an instance of a separate class,
+ * so it must be recognized by checking that `name` is `"OverlapError"`, rather than with `instanceof`.
+ */
+export interface OverlapError extends Error {
+    name: "OverlapError";
+    /**
+     * The existing clips that are in the way.
+     */
+    clips: AudioClip[];
+}
+
+/**
+ * The state of a deleted clip, including its gain and fades.
+ */
+export abstract class ClipState extends ExposedObject {}
+export class AudioClipState extends ClipState {
+    #brand: "AudioClipState";
+    private constructor();
+}
+
+/**
+ * The state needed to undo an edit spanning multiple clips and tracks.
+ *
+ * Obtained from {@linkcode Engine.insertTime()}, {@linkcode Engine.removeTime()}, {@linkcode Engine.cutTime()} or {@linkcode AudioClip.split()}.
+ */
+export class TimelineEditState extends ExposedObject {
+    #brand: "TimelineEditState";
+    private constructor();
+}
+
+/**
+ * When live input is played through an {@linkcode AudioTrack}, from the channels given by {@linkcode AudioTrack.setInputChannels()}.
+ */
+export enum MonitorMode {
+    /**
+     * Never play live input.
+     */
+    Off = "off",
+    /**
+     * Play live input while the track is record-armed,
+     * except while playing back without recording, so existing clips can be heard.
+     */
+    Auto = "auto",
+    /**
+     * Always play live input.
+     */
+    Always = "always",
+}
+
+/**
+ * Latencies of the engine in samples, as returned by {@linkcode Engine.getLatency()}.
+ */
+export interface Latency {
+    /**
+     * From sound reaching the input device to it being received by the engine.
+     */
+    input: number;
+    /**
+     * From the engine sending sound to the output device to it being heard.
+     */
+    output: number;
+    /**
+     * Added by processing within the engine.
+     */
+    processing: number;
+}
+
+/**
+ * Performance statistics of the audio thread, as returned by {@linkcode Engine.getStats()}.
+ */
+export interface Stats {
+    /**
+     * The average time spent processing each buffer, in percent of the time the buffer lasts.
+     * At 100 or above, the engine cannot keep up, and xruns happen.
+     */
+    averageLoad: number;
+    /**
+     * The longest time spent processing a single buffer, in percent of the time the buffer lasts.
+     */
+    peakLoad: number;
+    /**
+     * The number of times the output device ran out of samples to play.
+     */
+    underruns: number;
+    /**
+     * The number of times samples from the input device were dropped because they were not read in time.
+     */
+    overruns: number;
+    /**
+     * The buffer size in samples that the device actually delivers,
+     * which might differ from the one requested in {@linkcode config.OutputConfig.bufferSize}.
+     */
+    bufferSize: number;
+}
+
+/**
+ * Reported by {@linkcode Engine.onXrun()}.
+ */
+export interface XrunEvent {
+    /**
+     * The number of underruns since the last event.
+     */
+    underruns: number;
+    /**
+     * The number of overruns since the last event.
+     */
+    overruns: number;
+    /**
+     * The stats at the time of the event.
+     * If these were reset since the last event, e.g. because the stream was restarted,
+     * `underruns` and `overruns` are the counts since the reset.
+     */
+    stats: Stats;
+}
+
+export interface TimeRange {
+    start: Timestamp;
+    end: Timestamp;
+}
+
+/**
+ * A clip with multiple takes, as returned by {@linkcode AudioTrack.getTakeRegions()}.
+ */
+export interface TakeRegion {
+    clip: AudioClip;
+    /**
+     * Same as {@linkcode AudioClip.getTakes()}.
+     */
+    takes: StoredAudioClip[];
+    /**
+     * Same as {@linkcode AudioClip.getActiveTake()}.
+     */
+    activeTake: number;
+}
+
+/**
+ * A range of time copied from a set of audio tracks, as returned by {@linkcode Engine.copyTime()}.
+ *
+ * This is a plain object that can be serialized with `JSON.stringify()`.
+ * All times are given in beat units (see {@linkcode Timestamp.getBeatUnits()}), and clips are cropped to the copied range.
+ */
+export interface ClipboardData {
+    /**
+     * The length of the copied range.
+     */
+    length: number;
+    /**
+     * The clips copied from each track, in the order the tracks were given.
+     */
+    tracks: {
+        /**
+         * The key of the {@linkcode StoredAudioClip} that the clip plays.
+         */
+        storedClip: number;
+        /**
+         * The start of the clip, relative to the start of the copied range.
+         */
+        start: number;
+        length: number;
+        sourceOffset: number;
+        gain: number;
+        fadeIn: { length: number; curve: FadeCurve };
+        fadeOut: { length: number; curve: FadeCurve };
+    }[][];
+}
+
+/**
+ * A timestamp is either a point in time or a duration on the timeline,
+ * which can be represented in different units.
+ */
+export class Timestamp extends ExposedObject {
+    #brand: "Timestamp";
+    private constructor();
+
+    /**
+     * Return the smallest of the two timestamps.
+     */
+    static min(a: Timestamp, b: Timestamp): Timestamp;
+    /**
+     * Return the largest of the two timestamps.
+     */
+    static max(a: Timestamp, b: Timestamp): Timestamp;
+    /**
+     * Check whether the two timestamps are equal to each other .
+     */
+    static eq(a: Timestamp, b: Timestamp): boolean;
+    /**
+     * Compare `a` to `b`, returning a negative number if `a` comes first, a positive number if `b` comes first, and 0 if they are equal.
+     *
+     * This can be passed directly to {@linkcode Array.sort()}.
+     */
+    static cmp(a: Timestamp, b: Timestamp): -1 | 0 | 1;
+    /**
+     * Whether `a` is less than `b`.
+     */
+    static lt(a: Timestamp, b: Timestamp): boolean;
+    /**
+     * Whether `a` is greater than `b`.
+     */
+    static gt(a: Timestamp, b: Timestamp): boolean;
+
+    /**
+     * Add `a` to `b`.
+     */
+    static add(a: Timestamp, b: Timestamp): Timestamp;
+    /**
+     * Subtract `b` from `a`.
+     * Throws {@linkcode RangeError} if result is less than zero.
+     */
+    static sub(a: Timestamp, b: Timestamp): Timestamp;
+    /**
+     * Multiplies timestamp `ts` with scalar `s`.
+     * `s` will be truncated to an integer, and a
+     * {@linkcode RangeError} will be thrown if `s` is less than zero.
+     */
+    static mul(ts: Timestamp, s: number): Timestamp;
+    /**
+     * How many whole times `b` fits in `a`.
+     * Throws {@linkcode RangeError} if `b` is zero.
+     */
+    static div(a: Timestamp, b: Timestamp): number;
+    /**
+     * The remainder after dividing `a` by `b`, i.e. the part of `a` that is left after removing {@linkcode Timestamp.div()|Timestamp.div(a, b)} times `b`.
+     * Throws {@linkcode RangeError} if `b` is zero.
+     */
+    static mod(a: Timestamp, b: Timestamp): Timestamp;
+
+    /**
+     * Snap `ts` to a grid with lines every `step`, starting from zero.
+     * Throws {@linkcode RangeError} if the result would not be representable.
+     *
+     * @param mode  Defaults to {@linkcode SnapMode.Nearest}.
+     * @param swing How far every second line is delayed, as a fraction of `step`. Must be at least 0 and less than 1. Defaults to 0.
+     *              For example, 1/3 results in a triplet feel.
+     */
+    static snap(
+        ts: Timestamp,
+        step: Timestamp,
+        mode?: SnapMode,
+        swing?: number,
+    ): Timestamp;
+    /**
+     * Get every line from `start` up to, but not including, `end` on a grid with lines every `step`, starting from zero.
+     *
+     * Throws a {@linkcode RangeError} if there would be more than 65536 lines, instead of truncating the result,
+     * so split a long range into shorter ones if needed.
+     *
+     * @param swing How far every second line is delayed, as a fraction of `step`. Must be at least 0 and less than 1. Defaults to 0.
+     *              See {@linkcode Timestamp.snap()}.
+     */
+    static grid(
+        start: Timestamp,
+        end: Timestamp,
+        step: Timestamp,
+        swing?: number,
+    ): Timestamp[];
+
+    /**
+     * The smallest possible timestamp representing the very beginning (regardless of unit).
+     */
+    static zero(): Timestamp;
+    /**
+     * The largest representable timestamp, convenient for comparisons.
+     * Converting this to anything other than beat units may overflow.
+     */
+    static infinity(): Timestamp;
+
+    /**
+     * Create timestamp from beat units.
      * Parameter is truncated to an integer, and must be representable by a 32-bit unsigned integer.
+     *
+     * 1 beat = 1024 beat units
+     */
+    static fromBeatUnits(beatUnits: number): Timestamp;
+    /**
+     * Recreate a timestamp from the output of {@linkcode Timestamp.toJSON()}, with the exact same value.
+     * `beatUnits` must be an integer representable by a 32-bit unsigned integer.
+     */
+    static fromJSON(json: { beatUnits: number }): Timestamp;
+    /**
+     * Create timestamp from beats.
+     * Parameter may be fractional, and is rounded to the nearest beat unit, which must be representable by a 32-bit unsigned integer.
+     *
+     * 1 beat = 1024 beat units
      */
     static fromBeats(beats: number): Timestamp;
+    /**
+     * Create timestamp from seconds at the given tempo.
+     * Parameter is rounded to the nearest beat unit, which must be representable by a 32-bit unsigned integer.
+     */
+    static fromSeconds(seconds: number, bpm: number): Timestamp;
     /**
      * Create timestamp from samples.
      * `samples` parameter is truncated to an integer, and must be representable by a 64-bit unsigned integer.
@@ -454,6 +1287,105 @@ export class Timestamp extends ExposedObject {
     getBeatUnits(): number;
     getBeats(): number;
     getSamples(sampleRate: number, bpm: number): number;
+    /**
+     * Get the timestamp in seconds at the given tempo.
+     */
+    toSeconds(bpm: number): number;
+
+    /**
+     * Get a plain object holding the exact value of the timestamp, which can be passed to {@linkcode Timestamp.fromJSON()}.
+     *
+     * This is called by `JSON.stringify()`.
+     */
+    toJSON(): { beatUnits: number };
+    /**
+     * Get a human readable representation of the timestamp, intended for debugging.
+     */
+    toString(): string;
+    /**
+     * Get the timestamp in beat units, like {@linkcode Timestamp.getBeatUnits()}.
+     *
+     * This allows timestamps to be converted to numbers with unary `+`, and compared directly with `<` and `>` in plain JavaScript.
+     */
+    valueOf(): number;
+}
+
+/**
+ * A signed duration on the timeline, which can move a {@linkcode Timestamp} either forwards or backwards.
+ *
+ * Like {@linkcode Timestamp}, this is stored exactly in beat units, and its magnitude must be representable by a 32-bit unsigned integer.
+ */
+export class Offset extends ExposedObject {
+    #brand: "Offset";
+    private constructor();
+
+    /**
+     * An offset that does not move anything.
+     */
+    static zero(): Offset;
+    /**
+     * Create offset from beat units, which may be negative.
+     * Parameter is truncated to an integer.
+     *
+     * 1 beat = 1024 beat units
+     */
+    static fromBeatUnits(beatUnits: number): Offset;
+    /**
+     * Create offset from beats, which may be negative and fractional.
+     * Parameter is rounded to the nearest beat unit.
+     */
+    static fromBeats(beats: number): Offset;
+    /**
+     * The offset that moves `from` to `to`, which is negative if `to` comes before `from`.
+     */
+    static between(from: Timestamp, to: Timestamp): Offset;
+    /**
+     * The offset with the same magnitude as `o`, in the opposite direction.
+     */
+    static neg(o: Offset): Offset;
+    /**
+     * Add `a` to `b`.
+     * Throws {@linkcode RangeError} if the magnitude of the result is too large.
+     */
+    static add(a: Offset, b: Offset): Offset;
+    /**
+     * Check whether the two offsets are equal to each other.
+     */
+    static eq(a: Offset, b: Offset): boolean;
+    /**
+     * Move `ts` by `o`.
+     *
+     * If the result would lie before zero or after {@linkcode Timestamp.infinity()},
+     * this throws a {@linkcode RangeError}, unless `clamp` is `true`, in which case the result is clamped to that range.
+     */
+    static apply(ts: Timestamp, o: Offset, clamp?: boolean): Timestamp;
+
+    /**
+     * 1 beat = 1024 beat units
+     */
+    getBeatUnits(): number;
+    /**
+     * May be fractional.
+     */
+    getBeats(): number;
+}
+
+/**
+ * How to snap a timestamp to a grid with {@linkcode Timestamp.snap()}.
+ */
+export enum SnapMode {
+    /**
+     * Snap to the closest line. Timestamps exactly between two lines snap to the later one.
+     */
+    Nearest = "nearest",
+    /**
+     * Snap to the closest line at or before the timestamp.
+     */
+    Floor = "floor",
+    /**
+     * Snap to the closest line at or after the timestamp.
+     */
+    Ceil = "ceil",
 }
 
 /**
@@ -502,10 +1434,174 @@ export namespace config {
          */
         static default(): Config;
 
-        constructor(outputDevice: OutputDevice, outputConfig: OutputConfig);
+        /**
+         * Recreate a configuration from the output of {@linkcode Config.toJSON()}.
+         *
+         * The host and output device are looked up by name.
+         * If the output device no longer exists, the default output device of the host is used instead,
+         * and if the host no longer exists either, the default output device of the default host is used.
+         * In both cases the stored {@linkcode OutputConfig} is replaced by the default config of the new device.
+         *
+         * Throws an {@linkcode Error} if the device was not found and there is no default output device to fall back to.
+         *
+         * The input device is looked up the same way, but if it is not found, the config is left without input.
+         *
+         * `fallback` lists every replacement that was made, with the one for the output device first.
+         * If all stored devices were found, it is empty.
+         */
+        static fromJSON(json: ConfigJSON): {
+            config: Config;
+            fallback: Fallback[];
+        };
+
+        /**
+         * If `inputDevice` is left out, the engine has no input, and cannot record.
+         * If `inputConfig` is left out, the default config of the input device is used.
+         * If `threadConfig` is left out, the audio thread uses the default {@linkcode ThreadConfig}.
+         *
+         * Throws a {@linkcode RangeError} if `threadConfig` is invalid, see {@linkcode Config.withThreadConfig()}.
+         */
+        constructor(
+            outputDevice: OutputDevice,
+            outputConfig: OutputConfig,
+            inputDevice?: InputDevice | null,
+            inputConfig?: InputConfig,
+            threadConfig?: ThreadConfig | null,
+        );
 
         getOutputDevice(): OutputDevice;
         getOutputConfig(): OutputConfig;
+        getInputDevice(): InputDevice | null;
+        getInputConfig(): InputConfig | null;
+        getThreadConfig(): ThreadConfig;
+        /**
+         * Get a copy of this config, using `threadConfig` for the audio thread.
+         *
+         * Throws a {@linkcode RangeError} if `threadConfig.cpuAffinity` is empty,
+         * if a CPU index is not an integer from 0 to 65535,
+         * or if `threadConfig.workerThreads` is not an integer from 1 to 65535.
+         */
+        withThreadConfig(threadConfig: ThreadConfig): Config;
+
+        /**
+         * Get a plain object that can be stored, and passed to {@linkcode Config.fromJSON()} on a later launch.
+         *
+         * This is called by `JSON.stringify()`.
+         */
+        toJSON(): ConfigJSON;
+    }
+
+    /**
+     * A {@linkcode Config} that can be stored, as returned by {@linkcode Config.toJSON()}.
+     */
+    interface ConfigJSON {
+        /**
+         * The name of the {@linkcode Host} of the output device.
+         */
+        host: string;
+        /**
+         * The name of the {@linkcode OutputDevice}.
+         */
+        outputDevice: string;
+        outputConfig: OutputConfig;
+        /**
+         * The input device and its config, if any.
+         */
+        input?: {
+            /**
+             * The name of the {@linkcode Host} of the input device.
+             */
+            host: string;
+            /**
+             * The name of the {@linkcode InputDevice}.
+             */
+            device: string;
+            config: InputConfig;
+        } | null;
+        /**
+         * If left out or `null`, the default thread config is used.
+         */
+        threadConfig?: ThreadConfig | null;
+    }
+
+    /**
+     * Scheduling options for the audio thread.
+     */
+    interface ThreadConfig {
+        /**
+         * Whether to request real-time priority for the audio thread,
+         * e.g. `SCHED_FIFO` through rtkit on Linux.
+         * Whether it was granted is reported by {@linkcode Engine.getRealtimePriority()}.
+         */
+        realtimePriority: boolean;
+        /**
+         * The indices of the CPUs that the audio thread may run on, starting from 0.
+         * If `null`, it may run on any CPU.
+         */
+        cpuAffinity: number[] | null;
+        /**
+         * The number of threads that render tracks in parallel with the audio thread.
+         * If `null`, this is chosen based on the number of CPUs.
+         */
+        workerThreads: number | null;
+    }
+
+    /**
+     * Whether the audio thread runs with real-time priority, as returned by {@linkcode Engine.getRealtimePriority()}.
+     */
+    enum RealtimePriority {
+        /**
+         * Real-time priority was not requested in the {@linkcode ThreadConfig}.
+         */
+        NotRequested = "notRequested",
+        Granted = "granted",
+        /**
+         * Real-time priority was requested, but the system did not allow it.
+         * On Linux, this usually means that neither rtkit nor an `rtprio` limit is set up for the user.
+         */
+        Denied = "denied",
+    }
+
+    /**
+     * What {@linkcode Config.fromJSON()} used instead of the stored output device, when it could not be found.
+     */
+    enum Fallback {
+        /**
+         * The host was found, but the device was not, so the default output device of the host was used.
+         */
+        DefaultOutputDevice = "defaultOutputDevice",
+        /**
+         * The host was not found, so the default output device of the default host was used.
+         */
+        DefaultHost = "defaultHost",
+        /**
+         * The input device was not found, so the config was left without input.
+         */
+        NoInputDevice = "noInputDevice",
+    }
+
+    /**
+     * Configuration of the input stream of the engine, used for recording.
+     *
+     * The input always uses the sample rate of the {@linkcode OutputConfig}.
+     */
+    interface InputConfig {
+        /**
+         * The number of channels to read from the input device.
+         */
+        channels: number;
+        /**
+         * The format of the input samples.
+         *
+         * This is converted right after the samples are received from the input device.
+         */
+        sampleFormat: SampleFormat;
+        /**
+         * The size of the input buffers in samples.
+         *
+         * If `null`, the default is used.
+         */
+        bufferSize: number | null;
     }
 
     /**
@@ -663,6 +1759,69 @@ export namespace config {
          * Get the default output device for this host.
          */
         defaultOutputDevice(): OutputDevice | null;
+        /**
+         * Get an array of all available input devices for this host.
+         * Might be empty.
+         */
+        inputDevices(): InputDevice[];
+        /**
+         * Get the default input device for this host, if any.
+         */
+        defaultInputDevice(): InputDevice | null;
+        /**
+         * Call `callback` whenever an output or input device of this host appears or disappears, or the default output or input device changes.
+         *
+         * Devices are polled every `interval` milliseconds, defaulting to 1000.
+         * The watcher does not keep the process alive on its own.
+         *
+         * Throws a {@linkcode RangeError} if `interval` is not a positive number.
+         */
+        watchDevices(
+            callback: (event: DeviceEvent) => void,
+            options?: { interval?: number | null } | null,
+        ): DeviceWatcher;
+    }
+
+    /**
+     * Returned by {@linkcode Host.watchDevices()}, {@linkcode Engine.onOutputDeviceLost()}, {@linkcode Engine.enableFailover()} and {@linkcode Engine.onXrun()}.
+     *
+     * Watching stops when the watcher is garbage collected, so keep a reference to it for as long as it is needed.
+     */
+    class DeviceWatcher extends ExposedObject {
+        #brand: "DeviceWatcher";
+        private constructor();
+
+        /**
+         * Stop watching. The callback will not be called again.
+         */
+        stop(): void;
+    }
+
+    /**
+     * A change in the output or input devices of a {@linkcode Host}.
+     */
+    interface DeviceEvent {
+        kind: DeviceEventKind;
+        /**
+         * Whether the change concerns the output or the input devices.
+         */
+        direction: DeviceDirection;
+        /**
+         * The name of the device that was added, removed or became the default.
+         * This is `null` if there is no longer a default device in this direction.
+         */
+        name: string | null;
+    }
+
+    enum DeviceEventKind {
+        Added = "added",
+        Removed = "removed",
+        DefaultChanged = "defaultChanged",
+    }
+
+    enum DeviceDirection {
+        Output = "output",
+        Input = "input",
     }
 
     /**
@@ -690,5 +1849,44 @@ export namespace config {
          * Get the default {@linkcode OutputConfigRange} for this output device.
          */
         defaultConfigRange(): OutputConfigRange;
+        /**
+         * Get the fields of `outputConfig` that this output device does not support,
+         * according to the {@linkcode OutputConfigRange} from {@linkcode OutputDevice.supportedConfigRanges()} that matches it best.
+         *
+         * The config is supported if this is empty.
+         * A `bufferSize` of `null` is always supported, and so is any buffer size if the range does not specify one.
+         */
+        validateConfig(outputConfig: OutputConfig): (keyof OutputConfig)[];
+        /**
+         * Get the supported {@linkcode OutputConfig} that is closest to `desired`.
+         *
+         * Matching the number of channels takes priority over the sample format,
+         * which takes priority over the sample rate, which takes priority over the buffer size.
+         *
+         * Throws an {@linkcode Error} if the device does not support any configs.
+         */
+        nearestSupportedConfig(desired: OutputConfig): OutputConfig;
+    }
+    /**
+     * An input device is a specific audio device that can be recorded from.
+     *
+     * It can be retrieved from a {@linkcode Host} through either {@linkcode Host.inputDevices()} or {@linkcode Host.defaultInputDevice()}.
+     */
+    class InputDevice extends ExposedObject {
+        #brand: "InputDevice";
+        private constructor();
+
+        /**
+         * Get the host that this input device belongs to.
+         */
+        host(): Host;
+        /**
+         * Get the name of the input device.
+         */
+        name(): string;
+        /**
+         * Get the default {@linkcode InputConfig} for this input device.
+         */
+        defaultConfig(): InputConfig;
     }
 }
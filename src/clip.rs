@@ -4,13 +4,21 @@ use crate::encapsulator;
 use crate::encapsulator::encapsulate;
 use crate::encapsulator::unpack_this;
 use crate::encapsulator::Method;
+use crate::offset::{self, offset};
 use crate::shared_engine::SharedEngine;
 use crate::stored_clip::stored_audio_clip;
 use crate::timestamp;
 use std::ops::Deref;
 
 pub mod audio_clip {
-    use crate::{track::audio_track, utils::ResultExt};
+    use crate::{
+        overlap::{self, OverlapMode},
+        timeline_edit,
+        track::audio_track,
+        utils::ResultExt,
+    };
+
+    use super::fade;
 
     use super::*;
 
@@ -49,6 +57,21 @@ pub mod audio_clip {
         Ok(state_js)
     }
 
+    pub fn encapsulate_states<'a, C>(
+        cx: &mut C,
+        states: impl IntoIterator<Item = adae::AudioClipState>,
+    ) -> JsResult<'a, JsArray>
+    where
+        C: Context<'a>,
+    {
+        let states_js = cx.empty_array();
+        for (i, state) in states.into_iter().enumerate() {
+            let state_js = encapsulate_state(cx, state)?;
+            states_js.set(cx, i as u32, state_js)?;
+        }
+        Ok(states_js)
+    }
+
     pub fn unpack_state<'a, C>(
         cx: &mut C,
         state_obj: Handle<'a, JsObject>,
@@ -62,6 +85,75 @@ pub mod audio_clip {
         Ok(state)
     }
 
+    pub fn construct_takes<'a>(
+        cx: &mut FunctionContext<'a>,
+        takes: Vec<adae::StoredAudioClipKey>,
+        shared_engine: &SharedEngine,
+    ) -> JsResult<'a, JsArray> {
+        let takes_js = JsArray::new(cx, takes.len());
+        for (i, take) in takes.into_iter().enumerate() {
+            let take_js = stored_audio_clip::construct(cx, take, shared_engine.clone())?;
+            takes_js.set(cx, i as u32, take_js)?;
+        }
+        Ok(takes_js)
+    }
+
+    /// Move the clip to `new_start` on the audio track, making room according to `overlap_mode`.
+    ///
+    /// If the clip can't be moved, it and the clips in the way are left as they were.
+    /// Returns the states of the clips or parts of clips that were deleted to make room.
+    fn move_clip<'a>(
+        cx: &mut FunctionContext<'a>,
+        shared_engine: &SharedEngine,
+        engine: &mut adae::Engine,
+        clip_key: adae::AudioClipKey,
+        new_start: adae::Timestamp,
+        new_audio_track_key: adae::AudioTrackKey,
+        overlap_mode: OverlapMode,
+    ) -> JsResult<'a, JsValue> {
+        let audio_track_key = engine.audio_clip_track_key(clip_key).or_throw(cx)?;
+        let new_timeline_track_key = engine
+            .audio_timeline_track_key(new_audio_track_key)
+            .or_else(|e| cx.throw_error(format!("Failed to get timeline track: {e}")))?;
+        let bpm_cents = engine.bpm_cents();
+        let length = engine.audio_clip(clip_key).or_throw(cx)?.length(bpm_cents);
+
+        let (removed, _) = timeline_edit::atomic(cx, engine, |cx, engine, edit| {
+            edit.record(cx, engine, audio_track_key, clip_key)?;
+
+            let room = overlap::make_room(
+                cx,
+                shared_engine,
+                engine,
+                edit,
+                new_audio_track_key,
+                new_start,
+                length,
+                Some(clip_key),
+                overlap_mode,
+            )?;
+            if room.length != length {
+                engine
+                    .audio_clip_crop_end(clip_key, room.length)
+                    .or_throw(cx)?;
+            }
+
+            if new_audio_track_key == audio_track_key {
+                engine
+                    .audio_clip_move(clip_key, new_start)
+                    .or_else(|e| cx.throw_error(format!("Failed to move clip: {e}")))?;
+            } else {
+                engine
+                    .audio_clip_move_to_track(clip_key, new_start, new_timeline_track_key)
+                    .or_else(|e| cx.throw_error(format!("Failed to move clip: {e}")))?;
+            }
+
+            room.finish(cx, engine, clip_key)
+        })?;
+
+        Ok(encapsulate_states(cx, removed)?.as_value(cx))
+    }
+
     const METHODS: &[(&str, Method)] = &[
         ("getKey", |mut cx| {
             unpack_this(
@@ -92,14 +184,58 @@ pub mod audio_clip {
             let new_start_js = cx.argument::<JsObject>(0)?;
             let new_start = timestamp(&mut cx, new_start_js)?;
 
+            let overlap_mode = overlap::argument_opt(&mut cx, 1)?.unwrap_or(OverlapMode::Reject);
+
             encapsulator::unpack_this(
                 &mut cx,
                 |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
                     shared_engine.with_inner(cx, |cx, engine| {
-                        engine
-                            .audio_clip_move(**clip_key, new_start)
-                            .or_else(|e| cx.throw_error(format!("Failed to move clip: {e}")))?;
-                        Ok(cx.undefined().as_value(cx))
+                        let audio_track_key =
+                            engine.audio_clip_track_key(**clip_key).or_throw(cx)?;
+                        move_clip(
+                            cx,
+                            shared_engine,
+                            engine,
+                            **clip_key,
+                            new_start,
+                            audio_track_key,
+                            overlap_mode,
+                        )
+                    })
+                },
+            )
+        }),
+        ("nudge", |mut cx| {
+            let delta_js = cx.argument::<JsObject>(0)?;
+            let delta = offset(&mut cx, delta_js)?;
+
+            let overlap_mode = overlap::argument_opt(&mut cx, 1)?.unwrap_or(OverlapMode::Reject);
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let start = engine.audio_clip(**clip_key).or_throw(cx)?.start();
+                        let new_start = match offset::apply(start, delta) {
+                            Some(new_start) => new_start,
+                            None => {
+                                return cx.throw_range_error(format!(
+                                    "Clip cannot be nudged outside the timeline: {start:?} + {delta} beat units"
+                                ))
+                            }
+                        };
+
+                        let audio_track_key =
+                            engine.audio_clip_track_key(**clip_key).or_throw(cx)?;
+                        move_clip(
+                            cx,
+                            shared_engine,
+                            engine,
+                            **clip_key,
+                            new_start,
+                            audio_track_key,
+                            overlap_mode,
+                        )
                     })
                 },
             )
@@ -112,21 +248,21 @@ pub mod audio_clip {
             let new_audio_track_key =
                 audio_track::unpack_audio_track_key(&mut cx, new_audio_track_js)?;
 
+            let overlap_mode = overlap::argument_opt(&mut cx, 2)?.unwrap_or(OverlapMode::Reject);
+
             encapsulator::unpack_this(
                 &mut cx,
                 |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
                     shared_engine.with_inner(cx, |cx, engine| {
-                        let new_timeline_track_key = engine
-                            .audio_timeline_track_key(new_audio_track_key)
-                            .or_else(|e| {
-                                cx.throw_error(format!("Failed to get timeline track: {e}"))
-                            })?;
-
-                        engine
-                            .audio_clip_move_to_track(**clip_key, new_start, new_timeline_track_key)
-                            .or_else(|e| cx.throw_error(format!("Failed to move clip: {e}")))?;
-
-                        Ok(cx.undefined().as_value(cx))
+                        move_clip(
+                            cx,
+                            shared_engine,
+                            engine,
+                            **clip_key,
+                            new_start,
+                            new_audio_track_key,
+                            overlap_mode,
+                        )
                     })
                 },
             )
@@ -167,6 +303,224 @@ pub mod audio_clip {
                 },
             )
         }),
+        ("split", |mut cx| {
+            let at_js = cx.argument::<JsObject>(0)?;
+            let at = timestamp(&mut cx, at_js)?;
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let audio_track_key =
+                            engine.audio_clip_track_key(**clip_key).or_throw(cx)?;
+                        let (right_key, state) =
+                            timeline_edit::atomic(cx, engine, |cx, engine, edit| {
+                                edit.record(cx, engine, audio_track_key, **clip_key)?;
+                                let right_key =
+                                    engine.audio_clip_split(**clip_key, at).or_else(|e| {
+                                        cx.throw_error(format!("Failed to split clip: {e}"))
+                                    })?;
+                                edit.record_created(right_key);
+                                Ok(right_key)
+                            })?;
+
+                        let left_js = construct(cx, **clip_key, shared_engine.clone())?;
+                        let right_js = construct(cx, right_key, shared_engine.clone())?;
+                        let state_js = encapsulate(cx, state, &[], &[])?;
+
+                        let result_js = cx.empty_object();
+                        result_js.set(cx, "left", left_js)?;
+                        result_js.set(cx, "right", right_js)?;
+                        result_js.set(cx, "state", state_js)?;
+                        Ok(result_js.as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("duplicate", |mut cx| {
+            let new_start_js = cx.argument::<JsObject>(0)?;
+            let new_start = timestamp(&mut cx, new_start_js)?;
+
+            let target_audio_track_key = match cx.argument_opt(1) {
+                Some(val) if !val.is_a::<JsUndefined, _>(&mut cx) => {
+                    let target_audio_track_js = val.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                    Some(audio_track::unpack_audio_track_key(
+                        &mut cx,
+                        target_audio_track_js,
+                    )?)
+                }
+                _ => None,
+            };
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let audio_track_key = match target_audio_track_key {
+                            Some(audio_track_key) => audio_track_key,
+                            None => engine.audio_clip_track_key(**clip_key).or_throw(cx)?,
+                        };
+                        let timeline_track_key = engine
+                            .audio_timeline_track_key(audio_track_key)
+                            .or_else(|e| {
+                            cx.throw_error(format!("Failed to get timeline track: {e}"))
+                        })?;
+
+                        let new_clip_key = engine
+                            .audio_clip_duplicate(**clip_key, new_start, timeline_track_key)
+                            .or_else(|e| {
+                                cx.throw_error(format!("Failed to duplicate clip: {e}"))
+                            })?;
+
+                        Ok(construct(cx, new_clip_key, shared_engine.clone())?.as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getGain", |mut cx| {
+            unpack_this_clip(&mut cx, |cx, clip| Ok(cx.number(clip.gain()).as_value(cx)))
+        }),
+        ("setGain", |mut cx| {
+            let gain_js = cx.argument::<JsNumber>(0)?;
+            let gain = gain_js.value(&mut cx) as f32;
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        engine.audio_clip_set_gain(**clip_key, gain).or_else(|e| {
+                            cx.throw_error(format!("Failed to set gain of clip: {e}"))
+                        })?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getFadeIn", |mut cx| {
+            unpack_this_clip(&mut cx, |cx, clip| {
+                Ok(fade::construct(cx, clip.fade_in())?.as_value(cx))
+            })
+        }),
+        ("setFadeIn", |mut cx| {
+            let fade_in = fade::argument(&mut cx, 0, adae::FadeCurve::Linear)?;
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        engine
+                            .audio_clip_set_fade_in(**clip_key, fade_in)
+                            .or_else(|e| {
+                                cx.throw_error(format!("Failed to set fade in of clip: {e}"))
+                            })?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getFadeOut", |mut cx| {
+            unpack_this_clip(&mut cx, |cx, clip| {
+                Ok(fade::construct(cx, clip.fade_out())?.as_value(cx))
+            })
+        }),
+        ("setFadeOut", |mut cx| {
+            let fade_out = fade::argument(&mut cx, 0, adae::FadeCurve::Linear)?;
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        engine
+                            .audio_clip_set_fade_out(**clip_key, fade_out)
+                            .or_else(|e| {
+                                cx.throw_error(format!("Failed to set fade out of clip: {e}"))
+                            })?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getSourceOffset", |mut cx| {
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let bpm_cents = engine.bpm_cents();
+
+                        let clip = engine.audio_clip(**clip_key).or_throw(cx)?;
+                        timestamp::construct(cx, clip.source_offset(bpm_cents))
+                    })
+                },
+            )
+        }),
+        ("slip", |mut cx| {
+            let delta_js = cx.argument::<JsObject>(0)?;
+            let delta = offset(&mut cx, delta_js)?;
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let bpm_cents = engine.bpm_cents();
+
+                        let clip = engine.audio_clip(**clip_key).or_throw(cx)?;
+                        let source_offset = clip.source_offset(bpm_cents);
+                        let length = clip.length(bpm_cents);
+                        let stored_clip = engine
+                            .stored_audio_clip(clip.stored_clip())
+                            .or_throw(cx)?;
+                        let stored_length = adae::Timestamp::from_samples(
+                            stored_clip.length(),
+                            stored_clip.sample_rate(),
+                            bpm_cents,
+                        );
+
+                        let forwards_error = format!(
+                            "Clip cannot be slipped forwards past the start of its stored clip: {source_offset:?} - {delta} beat units"
+                        );
+                        let backwards_error = format!(
+                            "Clip cannot be slipped backwards past the end of its stored clip: {source_offset:?} + {} beat units, with a length of {length:?} and a stored clip length of {stored_length:?}",
+                            -delta
+                        );
+
+                        // Moving the audio later on the timeline means starting earlier in the source.
+                        let new_source_offset = match offset::apply(source_offset, -delta) {
+                            Some(new_source_offset) => new_source_offset,
+                            None if 0 < delta => return cx.throw_range_error(forwards_error),
+                            None => return cx.throw_range_error(backwards_error),
+                        };
+                        if delta < 0 {
+                            let new_end = new_source_offset
+                                .checked_add(length)
+                                .unwrap_or(adae::Timestamp::infinity());
+                            if stored_length < new_end {
+                                return cx.throw_range_error(backwards_error);
+                            }
+                        }
+
+                        engine
+                            .audio_clip_set_source_offset(**clip_key, new_source_offset)
+                            .or_else(|e| cx.throw_error(format!("Failed to slip clip: {e}")))?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getTrack", |mut cx| {
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let audio_track_key =
+                            engine.audio_clip_track_key(**clip_key).or_throw(cx)?;
+
+                        let audio_track_js =
+                            audio_track::construct(cx, audio_track_key, shared_engine.clone())?;
+                        Ok(audio_track_js.as_value(cx))
+                    })
+                },
+            )
+        }),
         ("getStoredClip", |mut cx| {
             encapsulator::unpack_this(
                 &mut cx,
@@ -184,6 +538,53 @@ pub mod audio_clip {
                 },
             )
         }),
+        ("getTakes", |mut cx| {
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let takes = engine.audio_clip_takes(**clip_key).or_throw(cx)?.to_vec();
+                        Ok(construct_takes(cx, takes, shared_engine)?.as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getActiveTake", |mut cx| {
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let active_take = engine.audio_clip_active_take(**clip_key).or_throw(cx)?;
+                        Ok(cx.number(active_take as f64).as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("setActiveTake", |mut cx| {
+            let index_js = cx.argument::<JsNumber>(0)?;
+            let index = index_js.value(&mut cx);
+
+            encapsulator::unpack_this(
+                &mut cx,
+                |cx, (shared_engine, clip_key): &(SharedEngine, AudioClipKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let take_count = engine.audio_clip_takes(**clip_key).or_throw(cx)?.len();
+                        if index.fract() != 0.0 || index < 0.0 || take_count as f64 <= index {
+                            return cx.throw_range_error(format!(
+                                "Take index must be an integer less than the number of takes, which is {take_count}. Got {index}"
+                            ));
+                        }
+
+                        engine
+                            .audio_clip_set_active_take(**clip_key, index as usize)
+                            .or_else(|e| {
+                                cx.throw_error(format!("Failed to set active take of clip: {e}"))
+                            })?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
         ("getWaveform", |mut cx| {
             encapsulator::unpack_this(
                 &mut cx,
@@ -233,3 +634,96 @@ pub mod audio_clip {
     }
     impl Finalize for AudioClipStateWrapper {}
 }
+
+pub mod fade {
+    use super::*;
+
+    /// The returned object must adhere to the `Fade` interface defined in the `index.d.ts` file.
+    pub fn construct<'a, C>(cx: &mut C, fade: adae::Fade) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let fade_js = cx.empty_object();
+
+        let length = timestamp::construct(cx, fade.length)?;
+        let curve = fade_curve::construct(cx, &fade.curve)?;
+
+        fade_js.set(cx, "length", length)?;
+        fade_js.set(cx, "curve", curve)?;
+
+        Ok(fade_js)
+    }
+
+    /// Read a fade from the arguments `(length: Timestamp, curve?: FadeCurve)`, starting at index `i`.
+    ///
+    /// If the curve is left out, `default_curve` is used.
+    pub fn argument(
+        cx: &mut FunctionContext<'_>,
+        i: usize,
+        default_curve: adae::FadeCurve,
+    ) -> NeonResult<adae::Fade> {
+        let length_js = cx.argument::<JsObject>(i)?;
+        let length = timestamp(cx, length_js)?;
+
+        let curve = match cx.argument_opt(i + 1) {
+            Some(val) if !val.is_a::<JsUndefined, _>(cx) => {
+                let curve_js = val.downcast_or_throw::<JsString, _>(cx)?;
+                fade_curve::get(cx, curve_js)?
+            }
+            _ => default_curve,
+        };
+
+        Ok(adae::Fade { length, curve })
+    }
+}
+
+pub mod fade_curve {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [
+            ("Linear", "linear"),
+            ("EqualPower", "equalPower"),
+            ("Exponential", "exponential"),
+        ];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+
+    pub fn construct<'a, C>(cx: &mut C, fade_curve: &adae::FadeCurve) -> JsResult<'a, JsValue>
+    where
+        C: Context<'a>,
+    {
+        let fade_curve_str = match fade_curve {
+            adae::FadeCurve::Linear => "linear",
+            adae::FadeCurve::EqualPower => "equalPower",
+            adae::FadeCurve::Exponential => "exponential",
+        };
+
+        Ok(cx.string(fade_curve_str).as_value(cx))
+    }
+
+    pub fn get<'a, C>(cx: &mut C, str: Handle<'a, JsString>) -> NeonResult<adae::FadeCurve>
+    where
+        C: Context<'a>,
+    {
+        let fade_curve = match str.value(cx).as_str() {
+            "linear" => adae::FadeCurve::Linear,
+            "equalPower" => adae::FadeCurve::EqualPower,
+            "exponential" => adae::FadeCurve::Exponential,
+            _ => return cx.throw_error(format!("Invalid fade curve: {str:?}")),
+        };
+
+        Ok(fade_curve)
+    }
+}
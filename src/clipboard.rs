@@ -0,0 +1,335 @@
+use neon::prelude::*;
+
+use adae::Timestamp;
+
+use crate::clip::fade_curve;
+use crate::overlap::{self, OverlapMode};
+use crate::shared_engine::SharedEngine;
+use crate::timeline_edit;
+use crate::timestamp::difference;
+use crate::utils::ResultExt;
+
+/// A range of time copied from a set of audio tracks.
+///
+/// This only refers to stored clips by their keys, so it stays valid after the copied clips are changed or deleted.
+#[derive(Debug)]
+pub struct ClipboardData {
+    length: Timestamp,
+    /// The clips of each copied audio track, in the order the tracks were given.
+    tracks: Vec<Vec<ClipboardClip>>,
+}
+
+#[derive(Debug)]
+struct ClipboardClip {
+    stored_clip: adae::StoredAudioClipKey,
+    /// Relative to the start of the copied range.
+    start: Timestamp,
+    length: Timestamp,
+    source_offset: Timestamp,
+    gain: f32,
+    fade_in: adae::Fade,
+    fade_out: adae::Fade,
+}
+
+/// Copy the range from `start` to `end` on the given audio tracks.
+///
+/// Clips that straddle either end of the range are cropped to it.
+pub fn copy<'a, C>(
+    cx: &mut C,
+    engine: &adae::Engine,
+    audio_track_keys: &[adae::AudioTrackKey],
+    start: Timestamp,
+    end: Timestamp,
+) -> NeonResult<ClipboardData>
+where
+    C: Context<'a>,
+{
+    let bpm_cents = engine.bpm_cents();
+    let mut tracks = Vec::with_capacity(audio_track_keys.len());
+
+    for &audio_track_key in audio_track_keys {
+        let timeline_track_key = engine
+            .audio_timeline_track_key(audio_track_key)
+            .or_throw(cx)?;
+
+        let mut clips = Vec::new();
+        for clip in engine.audio_clips(timeline_track_key).or_throw(cx)? {
+            let clip_start = clip.start();
+            let clip_end = clip_start
+                .checked_add(clip.length(bpm_cents))
+                .unwrap_or(Timestamp::infinity());
+            if !(clip_start < end && start < clip_end) {
+                continue;
+            }
+
+            let new_start = Timestamp::max(clip_start, start);
+            let new_end = Timestamp::min(clip_end, end);
+            let length = difference(cx, new_end, new_start)?;
+
+            let source_offset = clip.source_offset(bpm_cents);
+            let cropped = difference(cx, new_start, clip_start)?;
+            let source_offset = match source_offset.checked_add(cropped) {
+                Some(source_offset) => source_offset,
+                None => {
+                    return cx.throw_range_error(format!(
+                    "Source offset of copied clip is out of range: {source_offset:?} + {cropped:?}"
+                ))
+                }
+            };
+
+            // Fades are only kept on the ends of the clip that are not cropped.
+            let fade_in = clip.fade_in();
+            let fade_in_length = if start <= clip_start {
+                Timestamp::min(fade_in.length, length)
+            } else {
+                Timestamp::zero()
+            };
+            let fade_out = clip.fade_out();
+            let fade_out_length = if clip_end <= end {
+                Timestamp::min(fade_out.length, difference(cx, length, fade_in_length)?)
+            } else {
+                Timestamp::zero()
+            };
+
+            clips.push(ClipboardClip {
+                stored_clip: clip.stored_clip(),
+                start: difference(cx, new_start, start)?,
+                length,
+                source_offset,
+                gain: clip.gain(),
+                fade_in: adae::Fade {
+                    length: fade_in_length,
+                    curve: fade_in.curve,
+                },
+                fade_out: adae::Fade {
+                    length: fade_out_length,
+                    curve: fade_out.curve,
+                },
+            });
+        }
+
+        clips.sort_by_key(|clip| clip.start);
+        tracks.push(clips);
+    }
+
+    Ok(ClipboardData {
+        length: difference(cx, end, start)?,
+        tracks,
+    })
+}
+
+/// Paste the clipboard at `at`, placing the clips of each copied track on the corresponding track in `audio_track_keys`.
+///
+/// If any clip can't be pasted, the timeline is left as it was.
+/// Returns the keys of the pasted clips.
+pub fn paste(
+    cx: &mut FunctionContext<'_>,
+    shared_engine: &SharedEngine,
+    engine: &mut adae::Engine,
+    data: &ClipboardData,
+    at: Timestamp,
+    audio_track_keys: &[adae::AudioTrackKey],
+    mode: OverlapMode,
+) -> NeonResult<Vec<adae::AudioClipKey>> {
+    if data.tracks.len() != audio_track_keys.len() {
+        return cx.throw_error(format!(
+            "Clipboard holds {} tracks, but {} were given to paste onto",
+            data.tracks.len(),
+            audio_track_keys.len()
+        ));
+    }
+
+    let mut placements = Vec::new();
+    for (clips, &audio_track_key) in data.tracks.iter().zip(audio_track_keys) {
+        for clip in clips {
+            let start = match at.checked_add(clip.start) {
+                Some(start) => start,
+                None => {
+                    return cx.throw_range_error(format!(
+                        "Clip cannot be pasted past the end of the timeline: {at:?} + {:?}",
+                        clip.start
+                    ))
+                }
+            };
+            placements.push((audio_track_key, start, clip));
+        }
+    }
+
+    // Paste every clip as one edit, so a failed paste leaves the timeline untouched.
+    let (pasted, _) = timeline_edit::atomic(cx, engine, |cx, engine, edit| {
+        let mut pasted = Vec::with_capacity(placements.len());
+        for (audio_track_key, start, clip) in placements {
+            let room = overlap::make_room(
+                cx,
+                shared_engine,
+                engine,
+                edit,
+                audio_track_key,
+                start,
+                clip.length,
+                None,
+                mode,
+            )?;
+
+            let timeline_track_key = engine
+                .audio_timeline_track_key(audio_track_key)
+                .or_throw(cx)?;
+            let key = engine
+                .add_audio_clip(
+                    timeline_track_key,
+                    clip.stored_clip,
+                    start,
+                    Some(room.length),
+                )
+                .or_else(|e| cx.throw_error(format!("Failed to paste clip: {e}")))?;
+            edit.record_created(key);
+
+            engine
+                .audio_clip_set_source_offset(key, clip.source_offset)
+                .or_else(|e| cx.throw_error(format!("Failed to paste clip: {e}")))?;
+            engine
+                .audio_clip_set_gain(key, clip.gain)
+                .or_else(|e| cx.throw_error(format!("Failed to paste clip: {e}")))?;
+            engine
+                .audio_clip_set_fade_in(key, clip.fade_in)
+                .or_else(|e| cx.throw_error(format!("Failed to paste clip: {e}")))?;
+            engine
+                .audio_clip_set_fade_out(key, clip.fade_out)
+                .or_else(|e| cx.throw_error(format!("Failed to paste clip: {e}")))?;
+
+            room.finish(cx, engine, key)?;
+            pasted.push(key);
+        }
+
+        Ok(pasted)
+    })?;
+
+    Ok(pasted)
+}
+
+/// Convert the clipboard to a plain object, which can be serialized with `JSON.stringify()`.
+///
+/// The returned object must adhere to the `ClipboardData` interface defined in the `index.d.ts` file.
+pub fn construct<'a, C>(cx: &mut C, data: &ClipboardData) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let data_js = cx.empty_object();
+
+    let length_js = cx.number(data.length.beat_units());
+    data_js.set(cx, "length", length_js)?;
+
+    let tracks_js = JsArray::new(cx, data.tracks.len());
+    for (i, clips) in data.tracks.iter().enumerate() {
+        let clips_js = JsArray::new(cx, clips.len());
+        for (j, clip) in clips.iter().enumerate() {
+            let clip_js = cx.empty_object();
+
+            let stored_clip: u32 = clip.stored_clip.into();
+            let stored_clip_js = cx.number(stored_clip);
+            clip_js.set(cx, "storedClip", stored_clip_js)?;
+            let start_js = cx.number(clip.start.beat_units());
+            clip_js.set(cx, "start", start_js)?;
+            let length_js = cx.number(clip.length.beat_units());
+            clip_js.set(cx, "length", length_js)?;
+            let source_offset_js = cx.number(clip.source_offset.beat_units());
+            clip_js.set(cx, "sourceOffset", source_offset_js)?;
+            let gain_js = cx.number(clip.gain);
+            clip_js.set(cx, "gain", gain_js)?;
+            let fade_in_js = construct_fade(cx, clip.fade_in)?;
+            clip_js.set(cx, "fadeIn", fade_in_js)?;
+            let fade_out_js = construct_fade(cx, clip.fade_out)?;
+            clip_js.set(cx, "fadeOut", fade_out_js)?;
+
+            clips_js.set(cx, j as u32, clip_js)?;
+        }
+        tracks_js.set(cx, i as u32, clips_js)?;
+    }
+    data_js.set(cx, "tracks", tracks_js)?;
+
+    Ok(data_js)
+}
+
+/// Read a clipboard from a plain object, as returned by [`construct`].
+pub fn get(cx: &mut FunctionContext<'_>, data_js: Handle<JsObject>) -> NeonResult<ClipboardData> {
+    let length = get_beat_units(cx, data_js, "length")?;
+
+    let tracks_js: Handle<JsArray> = data_js.get(cx, "tracks")?;
+    let tracks_js = tracks_js.to_vec(cx)?;
+    let mut tracks = Vec::with_capacity(tracks_js.len());
+    for clips_js in tracks_js {
+        let clips_js = clips_js.downcast_or_throw::<JsArray, _>(cx)?.to_vec(cx)?;
+        let mut clips = Vec::with_capacity(clips_js.len());
+        for clip_js in clips_js {
+            let clip_js = clip_js.downcast_or_throw::<JsObject, _>(cx)?;
+
+            let stored_clip_js: Handle<JsNumber> = clip_js.get(cx, "storedClip")?;
+            let stored_clip = stored_clip_js.value(cx);
+            if !is_u32(stored_clip) {
+                return cx.throw_range_error(format!("Invalid stored clip key: {stored_clip}"));
+            }
+
+            let gain_js: Handle<JsNumber> = clip_js.get(cx, "gain")?;
+            let gain = gain_js.value(cx) as f32;
+
+            let fade_in_js: Handle<JsObject> = clip_js.get(cx, "fadeIn")?;
+            let fade_out_js: Handle<JsObject> = clip_js.get(cx, "fadeOut")?;
+
+            clips.push(ClipboardClip {
+                stored_clip: adae::StoredAudioClipKey::from(stored_clip as u32),
+                start: get_beat_units(cx, clip_js, "start")?,
+                length: get_beat_units(cx, clip_js, "length")?,
+                source_offset: get_beat_units(cx, clip_js, "sourceOffset")?,
+                gain,
+                fade_in: get_fade(cx, fade_in_js)?,
+                fade_out: get_fade(cx, fade_out_js)?,
+            });
+        }
+        tracks.push(clips);
+    }
+
+    Ok(ClipboardData { length, tracks })
+}
+
+fn construct_fade<'a, C>(cx: &mut C, fade: adae::Fade) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let fade_js = cx.empty_object();
+
+    let length_js = cx.number(fade.length.beat_units());
+    fade_js.set(cx, "length", length_js)?;
+    let curve_js = fade_curve::construct(cx, &fade.curve)?;
+    fade_js.set(cx, "curve", curve_js)?;
+
+    Ok(fade_js)
+}
+
+fn get_fade(cx: &mut FunctionContext<'_>, fade_js: Handle<JsObject>) -> NeonResult<adae::Fade> {
+    let length = get_beat_units(cx, fade_js, "length")?;
+    let curve_js: Handle<JsString> = fade_js.get(cx, "curve")?;
+    let curve = fade_curve::get(cx, curve_js)?;
+
+    Ok(adae::Fade { length, curve })
+}
+
+/// Read a timestamp stored as a number of beat units from the property `key`.
+fn get_beat_units(
+    cx: &mut FunctionContext<'_>,
+    obj: Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Timestamp> {
+    let value_js: Handle<JsNumber> = obj.get(cx, key)?;
+    let value = value_js.value(cx);
+    if !is_u32(value) {
+        return cx.throw_range_error(format!(
+            "Clipboard must have {key} value that is a whole number of beat units representable by a 32-bit unsigned integer. Got {value}"
+        ));
+    }
+
+    Ok(Timestamp::from_beat_units(value as u32))
+}
+
+fn is_u32(value: f64) -> bool {
+    value.fract() == 0.0 && 0.0 <= value && value <= u32::MAX as f64
+}
@@ -2,7 +2,8 @@ use neon::prelude::*;
 use neon::result::Throw;
 
 use super::encapsulator::{self, encapsulate, Method};
-use crate::utils::ResultExt;
+use crate::device_watcher::{self, device_direction, device_event, DeviceSnapshot};
+use crate::utils::{is_nullish, ResultExt};
 
 pub fn module<'a>(cx: &mut ModuleContext<'a>) -> JsResult<'a, JsObject> {
     let module = cx.empty_object();
@@ -16,6 +17,18 @@ pub fn module<'a>(cx: &mut ModuleContext<'a>) -> JsResult<'a, JsObject> {
     let sample_format_obj = sample_format::object(cx)?;
     module.set(cx, "SampleFormat", sample_format_obj)?;
 
+    let fallback_obj = fallback::object(cx)?;
+    module.set(cx, "Fallback", fallback_obj)?;
+
+    let device_event_kind_obj = device_event::object(cx)?;
+    module.set(cx, "DeviceEventKind", device_event_kind_obj)?;
+
+    let device_direction_obj = device_direction::object(cx)?;
+    module.set(cx, "DeviceDirection", device_direction_obj)?;
+
+    let realtime_priority_obj = realtime_priority::object(cx)?;
+    module.set(cx, "RealtimePriority", realtime_priority_obj)?;
+
     Ok(module)
 }
 
@@ -29,6 +42,14 @@ pub mod config_class {
         let constructor = JsFunction::new(cx, |mut cx| {
             let output_device_js = cx.argument::<JsObject>(0)?;
             let output_config_js = cx.argument::<JsObject>(1)?;
+            let input = input_argument_opt(&mut cx, 2)?;
+            let thread = match cx.argument_opt(4) {
+                Some(val) if !is_nullish(&mut cx, val) => {
+                    let thread_config_js = val.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                    thread_config::get(&mut cx, thread_config_js)?
+                }
+                _ => adae::config::ThreadConfig::default(),
+            };
 
             output_device::unpack(&mut cx, output_device_js, |cx, output_device| {
                 let output_config = output_config::get(cx, output_config_js)?;
@@ -37,6 +58,8 @@ pub mod config_class {
                     adae::config::Config {
                         output_device: output_device.clone(),
                         output_config,
+                        input,
+                        thread,
                     },
                 )
             })
@@ -57,6 +80,34 @@ pub mod config_class {
         encapsulate(cx, ConfigWrapper(config), &[], METHODS)
     }
 
+    /// Read an optional input device from the argument at index `i`, and its config from the one after it.
+    ///
+    /// If the config is left out, the default config of the input device is used.
+    fn input_argument_opt(
+        cx: &mut FunctionContext<'_>,
+        i: usize,
+    ) -> NeonResult<Option<adae::config::Input>> {
+        let input_device_js = match cx.argument_opt(i) {
+            Some(val) if !val.is_a::<JsUndefined, _>(cx) && !val.is_a::<JsNull, _>(cx) => {
+                val.downcast_or_throw::<JsObject, _>(cx)?
+            }
+            _ => return Ok(None),
+        };
+        let input_config_js = match cx.argument_opt(i + 1) {
+            Some(val) if !val.is_a::<JsUndefined, _>(cx) => {
+                Some(val.downcast_or_throw::<JsObject, _>(cx)?)
+            }
+            _ => None,
+        };
+
+        let device = input_device::unpack(cx, input_device_js, |_, device| Ok(device.clone()))?;
+        let config = match input_config_js {
+            Some(input_config_js) => input_config::get(cx, input_config_js)?,
+            None => device.default_config().or_throw(cx)?,
+        };
+        Ok(Some(adae::config::Input { device, config }))
+    }
+
     pub fn unpack<'a, C, F, R>(cx: &mut C, obj: Handle<'a, JsObject>, callback: F) -> NeonResult<R>
     where
         C: Context<'a>,
@@ -74,9 +125,126 @@ pub mod config_class {
         encapsulator::unpack_this(cx, |cx, config: &ConfigWrapper| callback(cx, &config.0))
     }
 
-    const STATIC_METHODS: &[(&str, Method)] = &[("default", |mut cx| {
-        Ok(construct(&mut cx, adae::config::Config::default())?.as_value(&mut cx))
-    })];
+    const STATIC_METHODS: &[(&str, Method)] = &[
+        ("default", |mut cx| {
+            Ok(construct(&mut cx, adae::config::Config::default())?.as_value(&mut cx))
+        }),
+        ("fromJSON", |mut cx| {
+            let json_js = cx.argument::<JsObject>(0)?;
+            let host_name_js: Handle<JsString> = json_js.get(&mut cx, "host")?;
+            let host_name = host_name_js.value(&mut cx);
+            let device_name_js: Handle<JsString> = json_js.get(&mut cx, "outputDevice")?;
+            let device_name = device_name_js.value(&mut cx);
+            let output_config_js: Handle<JsObject> = json_js.get(&mut cx, "outputConfig")?;
+            let output_config = output_config::get(&mut cx, output_config_js)?;
+
+            let host = adae::config::Host::available().find(|host| host.name() == host_name);
+            let device = match &host {
+                Some(host) => host
+                    .output_devices()
+                    .or_throw(&mut cx)?
+                    .find(|device| device.name() == device_name),
+                None => None,
+            };
+
+            let mut fallbacks = Vec::new();
+
+            // Unlike the output device, a missing input device is left out rather than replaced.
+            let input_json_js: Handle<JsValue> = json_js.get(&mut cx, "input")?;
+            let input = if input_json_js.is_a::<JsNull, _>(&mut cx)
+                || input_json_js.is_a::<JsUndefined, _>(&mut cx)
+            {
+                None
+            } else {
+                let input_json_js = input_json_js.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                let input_host_name_js: Handle<JsString> = input_json_js.get(&mut cx, "host")?;
+                let input_host_name = input_host_name_js.value(&mut cx);
+                let input_device_name_js: Handle<JsString> =
+                    input_json_js.get(&mut cx, "device")?;
+                let input_device_name = input_device_name_js.value(&mut cx);
+                let input_config_js: Handle<JsObject> = input_json_js.get(&mut cx, "config")?;
+                let input_config = input_config::get(&mut cx, input_config_js)?;
+
+                let input_host =
+                    adae::config::Host::available().find(|host| host.name() == input_host_name);
+                let input_device = match input_host {
+                    Some(host) => host
+                        .input_devices()
+                        .or_throw(&mut cx)?
+                        .find(|device| device.name() == input_device_name),
+                    None => None,
+                };
+                if input_device.is_none() {
+                    fallbacks.push("noInputDevice");
+                }
+                input_device.map(|device| adae::config::Input {
+                    device,
+                    config: input_config,
+                })
+            };
+
+            let thread_config_js: Handle<JsValue> = json_js.get(&mut cx, "threadConfig")?;
+            let thread = if thread_config_js.is_a::<JsUndefined, _>(&mut cx)
+                || thread_config_js.is_a::<JsNull, _>(&mut cx)
+            {
+                adae::config::ThreadConfig::default()
+            } else {
+                let thread_config_js =
+                    thread_config_js.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                thread_config::get(&mut cx, thread_config_js)?
+            };
+
+            let config = match (host, device) {
+                (_, Some(output_device)) => adae::config::Config {
+                    output_device,
+                    output_config,
+                    input,
+                    thread,
+                },
+
+                // The device is gone, so the stored output config might not be supported by the one replacing it.
+                (host, None) => {
+                    let (host, fallback) = match host {
+                        Some(host) => (host.clone(), "defaultOutputDevice"),
+                        None => (adae::config::Host::default(), "defaultHost"),
+                    };
+                    let output_device = match host.default_output_device().or_throw(&mut cx)? {
+                        Some(output_device) => output_device,
+                        None => {
+                            return cx.throw_error(format!(
+                                "Output device {device_name:?} was not found, and host {:?} has no default output device",
+                                host.name()
+                            ))
+                        }
+                    };
+                    let output_config = output_device
+                        .default_config_range()
+                        .or_throw(&mut cx)?
+                        .default_config();
+
+                    // The output fallback is listed first, as it is the one that matters most.
+                    fallbacks.insert(0, fallback);
+                    adae::config::Config {
+                        output_device,
+                        output_config,
+                        input,
+                        thread,
+                    }
+                }
+            };
+
+            let result_js = cx.empty_object();
+            let config_js = construct(&mut cx, config)?;
+            result_js.set(&mut cx, "config", config_js)?;
+            let fallbacks_js = JsArray::new(&mut cx, fallbacks.len());
+            for (i, fallback) in fallbacks.into_iter().enumerate() {
+                let fallback_js = cx.string(fallback);
+                fallbacks_js.set(&mut cx, i as u32, fallback_js)?;
+            }
+            result_js.set(&mut cx, "fallback", fallbacks_js)?;
+            Ok(result_js.as_value(&mut cx))
+        }),
+    ];
 
     const METHODS: &[(&str, Method)] = &[
         ("getOutputDevice", |mut cx| {
@@ -89,6 +257,67 @@ pub mod config_class {
                 Ok(output_config::construct(cx, config.output_config.clone())?.as_value(cx))
             })
         }),
+        ("getInputDevice", |mut cx| {
+            unpack_this(&mut cx, |cx, config| match &config.input {
+                Some(input) => Ok(input_device::construct(cx, input.device.clone())?.as_value(cx)),
+                None => Ok(cx.null().as_value(cx)),
+            })
+        }),
+        ("getInputConfig", |mut cx| {
+            unpack_this(&mut cx, |cx, config| match &config.input {
+                Some(input) => Ok(input_config::construct(cx, input.config.clone())?.as_value(cx)),
+                None => Ok(cx.null().as_value(cx)),
+            })
+        }),
+        ("getThreadConfig", |mut cx| {
+            unpack_this(&mut cx, |cx, config| {
+                Ok(thread_config::construct(cx, &config.thread)?.as_value(cx))
+            })
+        }),
+        ("withThreadConfig", |mut cx| {
+            let thread_config_js = cx.argument::<JsObject>(0)?;
+            let thread = thread_config::get(&mut cx, thread_config_js)?;
+
+            unpack_this(&mut cx, |cx, config| {
+                let config = adae::config::Config {
+                    thread,
+                    ..config.clone()
+                };
+                Ok(construct(cx, config)?.as_value(cx))
+            })
+        }),
+        ("toJSON", |mut cx| {
+            unpack_this(&mut cx, |cx, config| {
+                let json_js = cx.empty_object();
+
+                let host_name = cx.string(config.output_device.host().name());
+                json_js.set(cx, "host", host_name)?;
+                let device_name = cx.string(config.output_device.name());
+                json_js.set(cx, "outputDevice", device_name)?;
+                let output_config_js = output_config::construct(cx, config.output_config.clone())?;
+                json_js.set(cx, "outputConfig", output_config_js)?;
+
+                let input_js = match &config.input {
+                    Some(input) => {
+                        let input_js = cx.empty_object();
+                        let host_name = cx.string(input.device.host().name());
+                        input_js.set(cx, "host", host_name)?;
+                        let device_name = cx.string(input.device.name());
+                        input_js.set(cx, "device", device_name)?;
+                        let input_config_js = input_config::construct(cx, input.config.clone())?;
+                        input_js.set(cx, "config", input_config_js)?;
+                        input_js.as_value(cx)
+                    }
+                    None => cx.null().as_value(cx),
+                };
+                json_js.set(cx, "input", input_js)?;
+
+                let thread_config_js = thread_config::construct(cx, &config.thread)?;
+                json_js.set(cx, "threadConfig", thread_config_js)?;
+
+                Ok(json_js.as_value(cx))
+            })
+        }),
     ];
 
     #[derive(Debug)]
@@ -181,6 +410,214 @@ mod output_config {
     }
 }
 
+mod input_config {
+    use super::*;
+
+    pub fn construct<'a, C>(
+        cx: &mut C,
+        input_config: adae::config::InputConfig,
+    ) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let input_config_js = cx.empty_object();
+
+        let channels = cx.number(input_config.channels as f64);
+        let sample_format = sample_format::construct(cx, &input_config.sample_format)?;
+
+        input_config_js.set(cx, "channels", channels)?;
+        input_config_js.set(cx, "sampleFormat", sample_format)?;
+
+        if let Some(buffer_size) = input_config.buffer_size {
+            let buffer_size = cx.number(buffer_size as f64);
+            input_config_js.set(cx, "bufferSize", buffer_size)?;
+        } else {
+            let null = cx.null();
+            input_config_js.set(cx, "bufferSize", null)?;
+        }
+
+        Ok(input_config_js)
+    }
+
+    pub fn get<'a, C>(
+        cx: &mut C,
+        obj: Handle<'a, JsObject>,
+    ) -> NeonResult<adae::config::InputConfig>
+    where
+        C: Context<'a>,
+    {
+        let channels_js: Handle<JsNumber> = obj.get(cx, "channels")?;
+        let sample_format_js: Handle<JsString> = obj.get(cx, "sampleFormat")?;
+        let buffer_size_js: Handle<JsValue> = obj.get(cx, "bufferSize")?;
+
+        let channels_f64 = channels_js.value(cx);
+        if (channels_f64 < 0.0) || ((u16::MAX as f64) < channels_f64) {
+            return cx.throw_error(format!(
+                "Number of channels must an integer representable as an unsigned 16-bit integer. Got {channels_f64:?}"
+            ));
+        }
+        let channels = channels_f64 as u16;
+
+        let sample_format = sample_format::get(cx, sample_format_js)?;
+
+        let buffer_size = if buffer_size_js.is_a::<JsNull, _>(cx) {
+            None
+        } else {
+            let buffer_size_number: Handle<JsNumber> = buffer_size_js.downcast_or_throw(cx)?;
+            let buffer_size_f64 = buffer_size_number.value(cx);
+            if (buffer_size_f64 < 0.0) || ((u32::MAX as f64) < buffer_size_f64) {
+                return cx.throw_error(format!(
+                    "Buffer size must be an integer representable as an unsigned 32-bit integer. Got {buffer_size_f64:?}"
+                ));
+            }
+            Some(buffer_size_f64 as u32)
+        };
+
+        Ok(adae::config::InputConfig {
+            channels,
+            sample_format,
+            buffer_size,
+        })
+    }
+}
+
+mod thread_config {
+    use super::*;
+
+    pub fn construct<'a, C>(
+        cx: &mut C,
+        thread_config: &adae::config::ThreadConfig,
+    ) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let thread_config_js = cx.empty_object();
+
+        let realtime_priority = cx.boolean(thread_config.realtime_priority);
+        thread_config_js.set(cx, "realtimePriority", realtime_priority)?;
+
+        let cpu_affinity = match &thread_config.cpu_affinity {
+            Some(cpus) => {
+                let cpus_js = JsArray::new(cx, cpus.len());
+                for (i, &cpu) in cpus.iter().enumerate() {
+                    let cpu_js = cx.number(cpu as f64);
+                    cpus_js.set(cx, i as u32, cpu_js)?;
+                }
+                cpus_js.as_value(cx)
+            }
+            None => cx.null().as_value(cx),
+        };
+        thread_config_js.set(cx, "cpuAffinity", cpu_affinity)?;
+
+        let worker_threads = match thread_config.worker_threads {
+            Some(worker_threads) => cx.number(worker_threads as f64).as_value(cx),
+            None => cx.null().as_value(cx),
+        };
+        thread_config_js.set(cx, "workerThreads", worker_threads)?;
+
+        Ok(thread_config_js)
+    }
+
+    pub fn get<'a, C>(
+        cx: &mut C,
+        obj: Handle<'a, JsObject>,
+    ) -> NeonResult<adae::config::ThreadConfig>
+    where
+        C: Context<'a>,
+    {
+        let realtime_priority_js: Handle<JsBoolean> = obj.get(cx, "realtimePriority")?;
+        let realtime_priority = realtime_priority_js.value(cx);
+
+        let cpu_affinity_js: Handle<JsValue> = obj.get(cx, "cpuAffinity")?;
+        let cpu_affinity = if cpu_affinity_js.is_a::<JsNull, _>(cx) {
+            None
+        } else {
+            let cpus_js = cpu_affinity_js
+                .downcast_or_throw::<JsArray, _>(cx)?
+                .to_vec(cx)?;
+            if cpus_js.is_empty() {
+                return cx
+                    .throw_range_error("CPU affinity must contain at least one CPU, or be null");
+            }
+            let mut cpus = Vec::with_capacity(cpus_js.len());
+            for cpu_js in cpus_js {
+                let cpu = cpu_js.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+                if cpu.fract() != 0.0 || cpu < 0.0 || (u16::MAX as f64) < cpu {
+                    return cx.throw_range_error(format!(
+                        "CPU index must be an integer representable as an unsigned 16-bit integer. Got {cpu}"
+                    ));
+                }
+                cpus.push(usize::from(cpu as u16));
+            }
+            Some(cpus)
+        };
+
+        let worker_threads_js: Handle<JsValue> = obj.get(cx, "workerThreads")?;
+        let worker_threads = if worker_threads_js.is_a::<JsNull, _>(cx) {
+            None
+        } else {
+            let worker_threads = worker_threads_js
+                .downcast_or_throw::<JsNumber, _>(cx)?
+                .value(cx);
+            if worker_threads.fract() != 0.0
+                || worker_threads < 1.0
+                || (u16::MAX as f64) < worker_threads
+            {
+                return cx.throw_range_error(format!(
+                    "Number of worker threads must be a positive integer representable as an unsigned 16-bit integer. Got {worker_threads}"
+                ));
+            }
+            Some(usize::from(worker_threads as u16))
+        };
+
+        Ok(adae::config::ThreadConfig {
+            realtime_priority,
+            cpu_affinity,
+            worker_threads,
+        })
+    }
+}
+
+pub mod realtime_priority {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [
+            ("NotRequested", "notRequested"),
+            ("Granted", "granted"),
+            ("Denied", "denied"),
+        ];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+
+    pub fn construct<'a, C>(
+        cx: &mut C,
+        realtime_priority: &adae::RealtimePriority,
+    ) -> JsResult<'a, JsValue>
+    where
+        C: Context<'a>,
+    {
+        let realtime_priority_str = match realtime_priority {
+            adae::RealtimePriority::NotRequested => "notRequested",
+            adae::RealtimePriority::Granted => "granted",
+            adae::RealtimePriority::Denied => "denied",
+        };
+
+        Ok(cx.string(realtime_priority_str).as_value(cx))
+    }
+}
+
 mod output_config_range {
     use super::*;
 
@@ -256,11 +693,102 @@ mod output_config_range {
         }),
     ];
 
+    /// Get the names of the fields of `output_config` that are not supported by `range`.
+    ///
+    /// A buffer size of `null` is always supported, and so is any buffer size if the range does not specify one.
+    pub fn unsupported_fields(
+        range: &adae::config::OutputConfigRange,
+        output_config: &adae::config::OutputConfig,
+    ) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+
+        if range.channels() != output_config.channels {
+            fields.push("channels");
+        }
+        if *range.sample_format() != output_config.sample_format {
+            fields.push("sampleFormat");
+        }
+        if !range.sample_rate().contains(&output_config.sample_rate) {
+            fields.push("sampleRate");
+        }
+        if let (Some(buffer_size), Some(supported)) =
+            (output_config.buffer_size, range.buffer_size())
+        {
+            if !supported.contains(&buffer_size) {
+                fields.push("bufferSize");
+            }
+        }
+
+        fields
+    }
+
+    /// Get the config supported by `range` that is closest to `desired`,
+    /// along with a score that is lower the closer it is.
+    ///
+    /// Matching the number of channels is weighted over matching the sample format,
+    /// which is weighted over the sample rate, which is weighted over the buffer size.
+    pub fn nearest(
+        range: &adae::config::OutputConfigRange,
+        desired: &adae::config::OutputConfig,
+    ) -> (adae::config::OutputConfig, (u16, bool, u32, u32)) {
+        let sample_rate = desired
+            .sample_rate
+            .clamp(*range.sample_rate().start(), *range.sample_rate().end());
+        let buffer_size = match (desired.buffer_size, range.buffer_size()) {
+            (Some(buffer_size), Some(supported)) => {
+                Some(buffer_size.clamp(*supported.start(), *supported.end()))
+            }
+            (buffer_size, _) => buffer_size,
+        };
+
+        let output_config = adae::config::OutputConfig {
+            channels: range.channels(),
+            sample_format: range.sample_format().clone(),
+            sample_rate,
+            buffer_size,
+        };
+        let score = (
+            output_config.channels.abs_diff(desired.channels),
+            output_config.sample_format != desired.sample_format,
+            output_config.sample_rate.abs_diff(desired.sample_rate),
+            match (output_config.buffer_size, desired.buffer_size) {
+                (Some(a), Some(b)) => a.abs_diff(b),
+                _ => 0,
+            },
+        );
+
+        (output_config, score)
+    }
+
     #[derive(Debug)]
     struct OutputConfigRangeWrapper(adae::config::OutputConfigRange);
     impl Finalize for OutputConfigRangeWrapper {}
 }
 
+mod fallback {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [
+            ("DefaultOutputDevice", "defaultOutputDevice"),
+            ("DefaultHost", "defaultHost"),
+            ("NoInputDevice", "noInputDevice"),
+        ];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+}
+
 mod sample_format {
     use super::*;
 
@@ -420,6 +948,62 @@ mod host {
                 Ok(output_devices_js.as_value(cx))
             })
         }),
+        ("inputDevices", |mut cx| {
+            unpack_this(&mut cx, |cx, host| {
+                let input_devices = host
+                    .input_devices()
+                    .or_throw(cx)?
+                    .map(|device| input_device::construct(cx, device))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let input_devices_js = cx.empty_array();
+                for (i, input_device_js) in input_devices.into_iter().enumerate() {
+                    input_devices_js.set(cx, i as u32, input_device_js)?;
+                }
+                Ok(input_devices_js.as_value(cx))
+            })
+        }),
+        ("defaultInputDevice", |mut cx| {
+            unpack_this(&mut cx, |cx, host| {
+                match host.default_input_device().or_throw(cx)? {
+                    Some(input_device) => {
+                        Ok(input_device::construct(cx, input_device)?.as_value(cx))
+                    }
+                    None => Ok(cx.null().as_value(cx)),
+                }
+            })
+        }),
+        ("watchDevices", |mut cx| {
+            let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+            let interval = device_watcher::interval_option(&mut cx, 1)?;
+
+            let host = unpack_this(&mut cx, |_, host| Ok(host.clone()))?;
+            let mut previous = DeviceSnapshot::take(&host);
+
+            let watcher = device_watcher::spawn(
+                &mut cx,
+                interval,
+                move || {
+                    let current = DeviceSnapshot::take(&host);
+                    let events = match (&previous, &current) {
+                        (Some(previous), Some(current)) => previous.diff(current),
+                        // Skip over any failed queries, so devices are not reported as removed.
+                        _ => Vec::new(),
+                    };
+                    if current.is_some() {
+                        previous = current;
+                    }
+                    Some(events)
+                },
+                move |cx, events| {
+                    for event in events {
+                        let event_js = device_event::construct(cx, event)?;
+                        device_watcher::call(cx, &callback, event_js)?;
+                    }
+                    Ok(())
+                },
+            )?;
+            Ok(watcher.as_value(&mut cx))
+        }),
         ("defaultOutputDevice", |mut cx| {
             unpack_this(&mut cx, |cx, host| {
                 let output_device_opt = host.default_output_device().or_throw(cx)?;
@@ -504,9 +1088,110 @@ mod output_device {
                 Ok(output_config_range::construct(cx, range)?.as_value(cx))
             })
         }),
+        ("validateConfig", |mut cx| {
+            let output_config_js = cx.argument::<JsObject>(0)?;
+            let output_config = output_config::get(&mut cx, output_config_js)?;
+
+            unpack_this(&mut cx, |cx, device| {
+                // Report the fields that are unsupported by the range that matches best.
+                let unsupported_fields = device
+                    .supported_config_ranges()
+                    .or_throw(cx)?
+                    .map(|range| output_config_range::unsupported_fields(&range, &output_config))
+                    .min_by_key(|fields| fields.len())
+                    .unwrap_or_else(|| {
+                        vec!["channels", "sampleFormat", "sampleRate", "bufferSize"]
+                    });
+
+                let unsupported_fields_js = cx.empty_array();
+                for (i, field) in unsupported_fields.into_iter().enumerate() {
+                    let field_js = cx.string(field);
+                    unsupported_fields_js.set(cx, i as u32, field_js)?;
+                }
+                Ok(unsupported_fields_js.as_value(cx))
+            })
+        }),
+        ("nearestSupportedConfig", |mut cx| {
+            let desired_js = cx.argument::<JsObject>(0)?;
+            let desired = output_config::get(&mut cx, desired_js)?;
+
+            unpack_this(&mut cx, |cx, device| {
+                let nearest = device
+                    .supported_config_ranges()
+                    .or_throw(cx)?
+                    .map(|range| output_config_range::nearest(&range, &desired))
+                    .min_by_key(|(_, score)| *score);
+
+                match nearest {
+                    Some((output_config, _)) => {
+                        Ok(output_config::construct(cx, output_config)?.as_value(cx))
+                    }
+                    None => cx.throw_error(format!(
+                        "Output device {:?} has no supported configs",
+                        device.name()
+                    )),
+                }
+            })
+        }),
     ];
 
     #[derive(Debug)]
     struct OutputDeviceWrapper(adae::config::OutputDevice);
     impl Finalize for OutputDeviceWrapper {}
 }
+
+mod input_device {
+    use super::*;
+
+    pub fn construct<'a, C>(
+        cx: &mut C,
+        input_device: adae::config::InputDevice,
+    ) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        encapsulate(cx, InputDeviceWrapper(input_device), &[], METHODS)
+    }
+
+    pub fn unpack<'a, C, F, R>(cx: &mut C, obj: Handle<'a, JsObject>, callback: F) -> NeonResult<R>
+    where
+        C: Context<'a>,
+        F: FnOnce(&mut C, &adae::config::InputDevice) -> Result<R, Throw>,
+    {
+        encapsulator::unpack(cx, obj, |cx, device: &InputDeviceWrapper| {
+            callback(cx, &device.0)
+        })
+    }
+
+    pub fn unpack_this<'a, F, R>(cx: &mut FunctionContext<'a>, callback: F) -> NeonResult<R>
+    where
+        F: FnOnce(&mut FunctionContext<'a>, &adae::config::InputDevice) -> Result<R, Throw>,
+    {
+        encapsulator::unpack_this(cx, |cx, device: &InputDeviceWrapper| {
+            callback(cx, &device.0)
+        })
+    }
+
+    const METHODS: &[(&str, Method)] = &[
+        ("host", |mut cx| {
+            unpack_this(&mut cx, |cx, device| {
+                Ok(host::construct(cx, device.host().clone())?.as_value(cx))
+            })
+        }),
+        ("name", |mut cx| {
+            unpack_this(&mut cx, |cx, device| {
+                Ok(cx.string(device.name()).as_value(cx))
+            })
+        }),
+        ("defaultConfig", |mut cx| {
+            unpack_this(&mut cx, |cx, device| {
+                let input_config = device.default_config().or_throw(cx)?;
+                Ok(input_config::construct(cx, input_config)?.as_value(cx))
+            })
+        }),
+    ];
+
+    #[derive(Debug)]
+    struct InputDeviceWrapper(adae::config::InputDevice);
+    impl Finalize for InputDeviceWrapper {}
+}
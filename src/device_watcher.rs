@@ -0,0 +1,327 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use neon::prelude::*;
+
+use crate::encapsulator::{encapsulate, unpack_this, Method};
+use crate::utils::is_nullish;
+
+/// How often devices are polled if no interval is given.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Whether a device is used for output or input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceDirection {
+    Output,
+    Input,
+}
+
+/// A change in the audio devices, as observed by polling.
+#[derive(Debug)]
+pub enum DeviceEvent {
+    /// A device with the given name appeared.
+    Added(DeviceDirection, String),
+    /// A device with the given name disappeared.
+    Removed(DeviceDirection, String),
+    /// The default device changed to the one with the given name, if any.
+    DefaultChanged(DeviceDirection, Option<String>),
+}
+
+/// The devices of a host at one point in time, identified by name.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeviceSnapshot {
+    pub output_devices: Vec<String>,
+    pub default_output_device: Option<String>,
+    pub input_devices: Vec<String>,
+    pub default_input_device: Option<String>,
+}
+impl DeviceSnapshot {
+    /// Returns `None` if the host could not be queried.
+    pub fn take(host: &adae::config::Host) -> Option<Self> {
+        let output_devices = host
+            .output_devices()
+            .ok()?
+            .map(|device| device.name().to_string())
+            .collect();
+        let default_output_device = host
+            .default_output_device()
+            .ok()?
+            .map(|device| device.name().to_string());
+        let input_devices = host
+            .input_devices()
+            .ok()?
+            .map(|device| device.name().to_string())
+            .collect();
+        let default_input_device = host
+            .default_input_device()
+            .ok()?
+            .map(|device| device.name().to_string());
+
+        Some(Self {
+            output_devices,
+            default_output_device,
+            input_devices,
+            default_input_device,
+        })
+    }
+
+    /// Get the events leading from this snapshot to `next`.
+    pub fn diff(&self, next: &Self) -> Vec<DeviceEvent> {
+        let mut events = Vec::new();
+
+        diff_devices(
+            &mut events,
+            DeviceDirection::Output,
+            &self.output_devices,
+            &next.output_devices,
+            &self.default_output_device,
+            &next.default_output_device,
+        );
+        diff_devices(
+            &mut events,
+            DeviceDirection::Input,
+            &self.input_devices,
+            &next.input_devices,
+            &self.default_input_device,
+            &next.default_input_device,
+        );
+
+        events
+    }
+}
+
+/// Push the events leading from the previous to the next devices and default device of one direction to `events`.
+fn diff_devices(
+    events: &mut Vec<DeviceEvent>,
+    direction: DeviceDirection,
+    previous: &[String],
+    next: &[String],
+    previous_default: &Option<String>,
+    next_default: &Option<String>,
+) {
+    for name in next {
+        if !previous.contains(name) {
+            events.push(DeviceEvent::Added(direction, name.clone()));
+        }
+    }
+    for name in previous {
+        if !next.contains(name) {
+            events.push(DeviceEvent::Removed(direction, name.clone()));
+        }
+    }
+    if previous_default != next_default {
+        events.push(DeviceEvent::DefaultChanged(direction, next_default.clone()));
+    }
+}
+
+/// Whether the output device is still available from its host.
+///
+/// Returns `None` if the host could not be queried.
+pub fn output_device_present(output_device: &adae::config::OutputDevice) -> Option<bool> {
+    let name = output_device.name();
+    let mut output_devices = output_device.host().output_devices().ok()?;
+    Some(output_devices.any(|device| device.name() == name))
+}
+
+/// Read the `interval` property of an optional options object at the given argument index.
+pub fn interval_option(cx: &mut FunctionContext<'_>, i: usize) -> NeonResult<Duration> {
+    let options_js = match cx.argument_opt(i) {
+        Some(val) if !is_nullish(cx, val) => val.downcast_or_throw::<JsObject, _>(cx)?,
+        _ => return Ok(DEFAULT_INTERVAL),
+    };
+
+    let interval_js: Handle<JsValue> = options_js.get(cx, "interval")?;
+    if is_nullish(cx, interval_js) {
+        return Ok(DEFAULT_INTERVAL);
+    }
+    let interval_ms = interval_js.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+    if !(interval_ms > 0.0 && interval_ms.is_finite()) {
+        return cx.throw_range_error(format!(
+            "Interval must be a positive number of milliseconds. Got {interval_ms}"
+        ));
+    }
+
+    Ok(Duration::from_secs_f64(interval_ms / 1000.0))
+}
+
+/// Call `poll` every `interval` on a background thread,
+/// and hand whatever it returns to `handle` on the JavaScript thread, unless it is empty.
+///
+/// Polling stops when `poll` returns `None`, when the returned watcher is stopped or garbage collected,
+/// or when the JavaScript thread shuts down.
+/// The watcher does not keep the process alive on its own.
+pub fn spawn<'a, T, P, H>(
+    cx: &mut FunctionContext<'a>,
+    interval: Duration,
+    poll: P,
+    handle: H,
+) -> JsResult<'a, JsObject>
+where
+    T: Send + 'static,
+    P: FnMut() -> Option<Vec<T>> + Send + 'static,
+    H: Fn(&mut TaskContext, Vec<T>) -> NeonResult<()> + Send + Sync + 'static,
+{
+    spawn_with_stop_flag(cx, interval, Arc::default(), poll, handle)
+}
+
+/// Like [`spawn`], but the watcher also stops once `stopped` is set, so it can be stopped from Rust as well.
+pub fn spawn_with_stop_flag<'a, T, P, H>(
+    cx: &mut FunctionContext<'a>,
+    interval: Duration,
+    stopped: Arc<AtomicBool>,
+    mut poll: P,
+    handle: H,
+) -> JsResult<'a, JsObject>
+where
+    T: Send + 'static,
+    P: FnMut() -> Option<Vec<T>> + Send + 'static,
+    H: Fn(&mut TaskContext, Vec<T>) -> NeonResult<()> + Send + Sync + 'static,
+{
+    let handle = Arc::new(handle);
+
+    let mut channel = cx.channel();
+    channel.unref(cx);
+
+    let thread_stopped = Arc::clone(&stopped);
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        if thread_stopped.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let events = match poll() {
+            Some(events) => events,
+            None => break,
+        };
+        if events.is_empty() {
+            continue;
+        }
+
+        let handle = Arc::clone(&handle);
+        let handle_stopped = Arc::clone(&thread_stopped);
+        let sent = channel.try_send(move |mut cx| {
+            // The watcher might have been stopped while this was queued.
+            if handle_stopped.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            handle(&mut cx, events)
+        });
+        // The JavaScript thread is shutting down, so there is no one left to notify.
+        if sent.is_err() {
+            break;
+        }
+    });
+
+    encapsulate(cx, DeviceWatcherWrapper(stopped), &[], METHODS)
+}
+
+/// Call `callback` with the given argument, from a context outside of any function call.
+pub fn call<'a, V>(
+    cx: &mut TaskContext<'a>,
+    callback: &Root<JsFunction>,
+    arg: Handle<'a, V>,
+) -> NeonResult<()>
+where
+    V: Value,
+{
+    let callback = callback.to_inner(cx);
+    let this = cx.undefined();
+    callback.call(cx, this, [arg.upcast()])?;
+    Ok(())
+}
+
+pub mod device_direction {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [("Output", "output"), ("Input", "input")];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+
+    pub fn construct<'a, C>(cx: &mut C, direction: DeviceDirection) -> JsResult<'a, JsString>
+    where
+        C: Context<'a>,
+    {
+        let str = match direction {
+            DeviceDirection::Output => "output",
+            DeviceDirection::Input => "input",
+        };
+        Ok(cx.string(str))
+    }
+}
+
+pub mod device_event {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [
+            ("Added", "added"),
+            ("Removed", "removed"),
+            ("DefaultChanged", "defaultChanged"),
+        ];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+
+    /// The returned object must adhere to the `DeviceEvent` interface defined in the `index.d.ts` file.
+    pub fn construct<'a, C>(cx: &mut C, event: DeviceEvent) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let (kind, direction, name) = match event {
+            DeviceEvent::Added(direction, name) => ("added", direction, Some(name)),
+            DeviceEvent::Removed(direction, name) => ("removed", direction, Some(name)),
+            DeviceEvent::DefaultChanged(direction, name) => ("defaultChanged", direction, name),
+        };
+
+        let event_js = cx.empty_object();
+        let kind_js = cx.string(kind);
+        event_js.set(cx, "kind", kind_js)?;
+        let direction_js = device_direction::construct(cx, direction)?;
+        event_js.set(cx, "direction", direction_js)?;
+        let name_js = match name {
+            Some(name) => cx.string(name).as_value(cx),
+            None => cx.null().as_value(cx),
+        };
+        event_js.set(cx, "name", name_js)?;
+
+        Ok(event_js)
+    }
+}
+
+const METHODS: &[(&str, Method)] = &[("stop", |mut cx| {
+    unpack_this(&mut cx, |cx, watcher: &DeviceWatcherWrapper| {
+        watcher.0.store(true, Ordering::Relaxed);
+        Ok(cx.undefined().as_value(cx))
+    })
+})];
+
+struct DeviceWatcherWrapper(Arc<AtomicBool>);
+impl Finalize for DeviceWatcherWrapper {
+    fn finalize<'a, C: Context<'a>>(self, _: &mut C) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
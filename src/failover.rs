@@ -0,0 +1,176 @@
+use neon::prelude::*;
+
+use crate::config::config_class;
+
+/// What the engine switches to when its output device disappears.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailoverTarget {
+    /// The default output device of the default host, using its default config.
+    DefaultOutputDevice,
+    /// A sink that discards all output, but keeps the engine running.
+    Dummy,
+}
+
+/// A switch made by [`fail_over`].
+#[derive(Debug)]
+pub struct Failover {
+    /// The name of the output device that was lost.
+    pub lost_output_device: String,
+    /// The target that was actually used, or why no target could be used.
+    pub target: Result<FailoverTarget, String>,
+    /// The config the engine uses after the switch, or `None` if the engine could not be locked to switch.
+    pub config: Option<adae::config::Config>,
+}
+
+/// Switch the engine to `target`, keeping the playhead position and whether it is playing.
+///
+/// `default_config` must be the result of [`default_config`] if `target` is [`FailoverTarget::DefaultOutputDevice`].
+/// It is queried by the caller, so the devices aren't queried while the engine is locked.
+/// If it is `None`, the dummy sink is used instead.
+pub fn fail_over(
+    engine: &mut adae::Engine,
+    lost_output_device: String,
+    default_config: Option<adae::config::Config>,
+) -> Failover {
+    let playhead_position = engine.playhead_position();
+    let playing = engine.is_playing();
+
+    // The input device is kept, so recording can continue if it is still there.
+    let input = engine.config().input.clone();
+    let thread = engine.config().thread.clone();
+
+    let switched = default_config.and_then(|config| {
+        engine
+            .set_config(config.clone())
+            .ok()
+            .map(|()| (FailoverTarget::DefaultOutputDevice, config))
+    });
+    let target = match switched {
+        Some((target, _)) => Ok(target),
+        None => {
+            let config = adae::config::Config {
+                input,
+                thread: thread.clone(),
+                ..adae::config::Config::dummy()
+            };
+            match engine.set_config(config) {
+                Ok(()) => Ok(FailoverTarget::Dummy),
+                Err(_) => {
+                    // The input device might have disappeared along with the output device.
+                    let config = adae::config::Config {
+                        thread,
+                        ..adae::config::Config::dummy()
+                    };
+                    engine
+                        .set_config(config)
+                        .map(|()| FailoverTarget::Dummy)
+                        .map_err(|e| format!("Failed to switch to the dummy output: {e}"))
+                }
+            }
+        }
+    };
+
+    engine.jump_to(playhead_position);
+    if playing {
+        engine.play();
+    }
+
+    Failover {
+        lost_output_device,
+        target,
+        config: Some(engine.config().clone()),
+    }
+}
+
+/// The failover reported when the engine can't be locked to switch, because a panic occurred while it was locked.
+pub fn poisoned(lost_output_device: String) -> Failover {
+    Failover {
+        lost_output_device,
+        target: Err("A panic has occurred while holding a lock on the engine.".to_string()),
+        config: None,
+    }
+}
+
+/// The config of the default output device of the default host, keeping `input` and `thread`.
+///
+/// Returns `None` if there is no such device, or if it could not be queried.
+pub fn default_config(
+    input: Option<adae::config::Input>,
+    thread: adae::config::ThreadConfig,
+) -> Option<adae::config::Config> {
+    let output_device = adae::config::Host::default()
+        .default_output_device()
+        .ok()??;
+    let output_config = output_device.default_config_range().ok()?.default_config();
+    Some(adae::config::Config {
+        output_device,
+        output_config,
+        input,
+        thread,
+    })
+}
+
+pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let obj = cx.empty_object();
+
+    let fields = [
+        ("DefaultOutputDevice", "defaultOutputDevice"),
+        ("Dummy", "dummy"),
+    ];
+
+    for (name, val) in fields.iter() {
+        let str = cx.string(*val);
+        obj.set(cx, *name, str)?;
+    }
+
+    Ok(obj)
+}
+
+pub fn get<'a, C>(cx: &mut C, str: Handle<'a, JsString>) -> NeonResult<FailoverTarget>
+where
+    C: Context<'a>,
+{
+    let target = match str.value(cx).as_str() {
+        "defaultOutputDevice" => FailoverTarget::DefaultOutputDevice,
+        "dummy" => FailoverTarget::Dummy,
+        other => return cx.throw_error(format!("Invalid failover target: {other:?}")),
+    };
+    Ok(target)
+}
+
+fn target_str(target: FailoverTarget) -> &'static str {
+    match target {
+        FailoverTarget::DefaultOutputDevice => "defaultOutputDevice",
+        FailoverTarget::Dummy => "dummy",
+    }
+}
+
+/// The returned object must adhere to the `FailoverEvent` interface defined in the `index.d.ts` file.
+pub fn construct<'a, C>(cx: &mut C, failover: Failover) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let failover_js = cx.empty_object();
+
+    let lost_output_device_js = cx.string(failover.lost_output_device);
+    failover_js.set(cx, "lostOutputDevice", lost_output_device_js)?;
+    let (target_js, error_js) = match failover.target {
+        Ok(target) => (
+            cx.string(target_str(target)).as_value(cx),
+            cx.null().as_value(cx),
+        ),
+        Err(error) => (cx.null().as_value(cx), cx.string(error).as_value(cx)),
+    };
+    failover_js.set(cx, "target", target_js)?;
+    failover_js.set(cx, "error", error_js)?;
+    let config_js = match failover.config {
+        Some(config) => config_class::construct(cx, config)?.as_value(cx),
+        None => cx.null().as_value(cx),
+    };
+    failover_js.set(cx, "config", config_js)?;
+
+    Ok(failover_js)
+}
@@ -1,33 +1,45 @@
 //! Binding for Node.js' native addon API.
 
 mod clip;
+mod clipboard;
 mod config;
 mod custom_output;
+mod device_watcher;
 mod encapsulator;
+mod failover;
+mod offset;
+mod overlap;
 mod panic_handling;
 mod shared_engine;
 mod stored_clip;
+mod timeline_edit;
 mod timestamp;
 mod track;
 mod utils;
 
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 use neon::prelude::*;
 
+use clip::audio_clip;
 use custom_output::get_debug;
 #[cfg(feature = "custom_debug_output")]
 use custom_output::output_debug;
 use encapsulator::{encapsulate, prevent_gc, unpack, unpack_this, Method};
+use failover::FailoverTarget;
+use overlap::OverlapMode;
 use panic_handling::{listen_for_crash, stop_listening_for_crash};
 use shared_engine::SharedEngine;
 use stored_clip::stored_audio_clip;
+use timeline_edit::TimelineEditStateWrapper;
 use timestamp::timestamp;
 use track::{
     audio_track::{self, AudioTrackStateWrapper},
     master,
 };
-use utils::ResultExt;
+use utils::{is_nullish, ResultExt};
 
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
@@ -50,6 +62,24 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     let timestamp_class = timestamp::class(&mut cx)?;
     cx.export_value("Timestamp", timestamp_class)?;
 
+    let offset_class = offset::class(&mut cx)?;
+    cx.export_value("Offset", offset_class)?;
+
+    let snap_mode_obj = timestamp::snap_mode::object(&mut cx)?;
+    cx.export_value("SnapMode", snap_mode_obj)?;
+
+    let fade_curve_obj = clip::fade_curve::object(&mut cx)?;
+    cx.export_value("FadeCurve", fade_curve_obj)?;
+
+    let overlap_mode_obj = overlap::object(&mut cx)?;
+    cx.export_value("OverlapMode", overlap_mode_obj)?;
+
+    let monitor_mode_obj = track::monitor_mode::object(&mut cx)?;
+    cx.export_value("MonitorMode", monitor_mode_obj)?;
+
+    let failover_target_obj = failover::object(&mut cx)?;
+    cx.export_value("FailoverTarget", failover_target_obj)?;
+
     let config_module = config::module(&mut cx)?;
     cx.export_value("config", config_module)?;
 
@@ -81,7 +111,26 @@ fn constructor(mut cx: FunctionContext) -> JsResult<JsObject> {
 }
 
 const STATIC_METHODS: &[(&str, Method)] = &[("getDummy", |mut cx| {
-    let shared_engine = SharedEngine::dummy();
+    let input = match cx.argument_opt(0) {
+        Some(options_js) if !is_nullish(&mut cx, options_js) => {
+            let options_js = options_js.downcast_or_throw::<JsObject, _>(&mut cx)?;
+            let input_js: Handle<JsValue> = options_js.get(&mut cx, "input")?;
+            if is_nullish(&mut cx, input_js) {
+                false
+            } else {
+                input_js
+                    .downcast_or_throw::<JsBoolean, _>(&mut cx)?
+                    .value(&mut cx)
+            }
+        }
+        _ => false,
+    };
+
+    let shared_engine = if input {
+        SharedEngine::dummy_with_input().or_throw(&mut cx)?
+    } else {
+        SharedEngine::dummy()
+    };
     let object = encapsulate(&mut cx, shared_engine, &[], METHODS)?;
     prevent_gc(&mut cx, object)?;
     Ok(object.as_value(&mut cx))
@@ -123,6 +172,40 @@ const METHODS: &[(&str, Method)] = &[
             })
         })
     }),
+    ("suspend", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                engine
+                    .suspend()
+                    .or_else(|e| cx.throw_error(format!("Failed to suspend audio stream: {e}")))?;
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("resume", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                engine
+                    .resume()
+                    .or_else(|e| cx.throw_error(format!("Failed to resume audio stream: {e}")))?;
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("getRealtimePriority", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                config::realtime_priority::construct(cx, &engine.realtime_priority())
+            })
+        })
+    }),
+    ("isSuspended", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                Ok(cx.boolean(engine.is_suspended()).as_value(cx))
+            })
+        })
+    }),
     ("jumpTo", |mut cx| {
         let timestamp_js: Handle<JsObject> = cx.argument(0)?;
         let timestamp = timestamp(&mut cx, timestamp_js)?;
@@ -142,6 +225,105 @@ const METHODS: &[(&str, Method)] = &[
             })
         })
     }),
+    ("getPunch", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                Ok(construct_range_opt(cx, engine.punch())?.as_value(cx))
+            })
+        })
+    }),
+    ("setPunch", |mut cx| {
+        let (start, end) = range_arguments(&mut cx, 0)?;
+        // An empty range can't be recorded over, so it clears the punch range instead.
+        let range = (start != end).then_some((start, end));
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                engine.set_punch(range);
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("clearPunch", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                engine.set_punch(None);
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("getLoop", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                Ok(construct_range_opt(cx, engine.loop_range())?.as_value(cx))
+            })
+        })
+    }),
+    ("setLoop", |mut cx| {
+        let (start, end) = range_arguments(&mut cx, 0)?;
+        // An empty range can't be looped over, so it clears the loop range instead.
+        let range = (start != end).then_some((start, end));
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                engine.set_loop(range);
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("clearLoop", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                engine.set_loop(None);
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("toSamples", |mut cx| {
+        let ts_js: Handle<JsObject> = cx.argument(0)?;
+        let ts = timestamp(&mut cx, ts_js)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let sample_rate = engine.config().output_config.sample_rate;
+                let samples = ts.samples(sample_rate, engine.bpm_cents());
+                Ok(cx.number(samples as f64).as_value(cx))
+            })
+        })
+    }),
+    ("fromSamples", |mut cx| {
+        let samples = timestamp::samples_argument(&mut cx, 0)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let sample_rate = engine.config().output_config.sample_rate;
+                let ts = adae::Timestamp::from_samples(samples, sample_rate, engine.bpm_cents());
+                timestamp::construct(cx, ts)
+            })
+        })
+    }),
+    ("toSeconds", |mut cx| {
+        let ts_js: Handle<JsObject> = cx.argument(0)?;
+        let ts = timestamp(&mut cx, ts_js)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let seconds = timestamp::to_seconds(ts, engine.bpm_cents());
+                Ok(cx.number(seconds).as_value(cx))
+            })
+        })
+    }),
+    ("fromSeconds", |mut cx| {
+        let seconds_js: Handle<JsNumber> = cx.argument(0)?;
+        let seconds = seconds_js.value(&mut cx);
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let ts = timestamp::from_seconds(cx, seconds, engine.bpm_cents())?;
+                timestamp::construct(cx, ts)
+            })
+        })
+    }),
     ("getMaster", |mut cx| {
         unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
             shared_engine.assert_not_closed(cx)?;
@@ -286,6 +468,177 @@ const METHODS: &[(&str, Method)] = &[
             })
         })
     }),
+    ("getClipsInRange", |mut cx| {
+        let (start, end) = range_arguments(&mut cx, 0)?;
+
+        let audio_track_keys = tracks_option(&mut cx, 2)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let bpm_cents = engine.bpm_cents();
+                let audio_track_keys = match audio_track_keys {
+                    Some(audio_track_keys) => audio_track_keys,
+                    None => engine.audio_tracks().collect(),
+                };
+
+                let mut clip_keys = Vec::new();
+                for audio_track_key in audio_track_keys {
+                    let timeline_track_key = engine
+                        .audio_timeline_track_key(audio_track_key)
+                        .or_throw(cx)?;
+                    let clips = engine.audio_clips(timeline_track_key).or_throw(cx)?;
+
+                    clip_keys.extend(
+                        clips
+                            .filter(|clip| {
+                                let clip_start = clip.start();
+                                let clip_end = clip_start
+                                    .checked_add(clip.length(bpm_cents))
+                                    .unwrap_or(adae::Timestamp::infinity());
+
+                                // An empty range still matches the clip it lies within.
+                                let starts_before_end = if start == end {
+                                    clip_start <= end
+                                } else {
+                                    clip_start < end
+                                };
+                                starts_before_end && start < clip_end
+                            })
+                            .map(|clip| clip.key),
+                    );
+                }
+
+                let clips_js = JsArray::new(cx, clip_keys.len());
+                for (i, clip_key) in clip_keys.into_iter().enumerate() {
+                    let clip_js = audio_clip::construct(cx, clip_key, shared_engine.clone())?;
+                    clips_js.set(cx, i as u32, clip_js)?;
+                }
+
+                Ok(clips_js.as_value(cx))
+            })
+        })
+    }),
+    ("insertTime", |mut cx| {
+        let at_js: Handle<JsObject> = cx.argument(0)?;
+        let at = timestamp(&mut cx, at_js)?;
+        let length_js: Handle<JsObject> = cx.argument(1)?;
+        let length = timestamp(&mut cx, length_js)?;
+
+        let audio_track_keys = tracks_option(&mut cx, 2)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let audio_track_keys = match audio_track_keys {
+                    Some(audio_track_keys) => audio_track_keys,
+                    None => engine.audio_tracks().collect(),
+                };
+
+                let state = timeline_edit::insert_time(cx, engine, &audio_track_keys, at, length)?;
+                Ok(encapsulate(cx, state, &[], &[])?.as_value(cx))
+            })
+        })
+    }),
+    ("removeTime", |mut cx| {
+        let (start, end) = range_arguments(&mut cx, 0)?;
+
+        let audio_track_keys = tracks_option(&mut cx, 2)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let audio_track_keys = match audio_track_keys {
+                    Some(audio_track_keys) => audio_track_keys,
+                    None => engine.audio_tracks().collect(),
+                };
+
+                let state = timeline_edit::remove_time(cx, engine, &audio_track_keys, start, end)?;
+                Ok(encapsulate(cx, state, &[], &[])?.as_value(cx))
+            })
+        })
+    }),
+    ("revertTimelineEdit", |mut cx| {
+        let state_js: Handle<JsObject> = cx.argument(0)?;
+        unpack(&mut cx, state_js, |cx, state: &TimelineEditStateWrapper| {
+            unpack_this(cx, |cx, shared_engine: &SharedEngine| {
+                shared_engine.with_inner(cx, |cx, engine| {
+                    timeline_edit::revert(cx, engine, state)?;
+                    Ok(cx.undefined().as_value(cx))
+                })
+            })
+        })
+    }),
+    ("copyTime", |mut cx| {
+        let (start, end) = range_arguments(&mut cx, 0)?;
+
+        let audio_track_keys = tracks_option(&mut cx, 2)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let audio_track_keys = match audio_track_keys {
+                    Some(audio_track_keys) => audio_track_keys,
+                    None => engine.audio_tracks().collect(),
+                };
+
+                let data = clipboard::copy(cx, engine, &audio_track_keys, start, end)?;
+                Ok(clipboard::construct(cx, &data)?.as_value(cx))
+            })
+        })
+    }),
+    ("cutTime", |mut cx| {
+        let (start, end) = range_arguments(&mut cx, 0)?;
+
+        let audio_track_keys = tracks_option(&mut cx, 2)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let audio_track_keys = match audio_track_keys {
+                    Some(audio_track_keys) => audio_track_keys,
+                    None => engine.audio_tracks().collect(),
+                };
+
+                let data = clipboard::copy(cx, engine, &audio_track_keys, start, end)?;
+                let state = timeline_edit::clear_time(cx, engine, &audio_track_keys, start, end)?;
+
+                let data_js = clipboard::construct(cx, &data)?;
+                let state_js = encapsulate(cx, state, &[], &[])?;
+
+                let result_js = cx.empty_object();
+                result_js.set(cx, "clipboard", data_js)?;
+                result_js.set(cx, "state", state_js)?;
+                Ok(result_js.as_value(cx))
+            })
+        })
+    }),
+    ("paste", |mut cx| {
+        let data_js: Handle<JsObject> = cx.argument(0)?;
+        let data = clipboard::get(&mut cx, data_js)?;
+        let at_js: Handle<JsObject> = cx.argument(1)?;
+        let at = timestamp(&mut cx, at_js)?;
+        let tracks_js: Handle<JsArray> = cx.argument(2)?;
+        let audio_track_keys = audio_track::unpack_audio_track_keys(&mut cx, tracks_js)?;
+        let overlap_mode = overlap::argument_opt(&mut cx, 3)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let clip_keys = clipboard::paste(
+                    cx,
+                    shared_engine,
+                    engine,
+                    &data,
+                    at,
+                    &audio_track_keys,
+                    overlap_mode.unwrap_or(OverlapMode::Reject),
+                )?;
+
+                let clips_js = JsArray::new(cx, clip_keys.len());
+                for (i, clip_key) in clip_keys.into_iter().enumerate() {
+                    let clip_js = audio_clip::construct(cx, clip_key, shared_engine.clone())?;
+                    clips_js.set(cx, i as u32, clip_js)?;
+                }
+
+                Ok(clips_js.as_value(cx))
+            })
+        })
+    }),
     ("importAudioClip", |mut cx| {
         let path_js: Handle<JsString> = cx.argument(0)?;
         let path = path_js.value(&mut cx);
@@ -299,6 +652,334 @@ const METHODS: &[(&str, Method)] = &[
             })
         })
     }),
+    ("record", |mut cx| {
+        let directory_js: Handle<JsString> = cx.argument(0)?;
+        let directory = directory_js.value(&mut cx);
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                if engine.config().input.is_none() {
+                    return cx.throw_error(
+                        "Cannot record without an input device. Set one with Engine.setConfig()",
+                    );
+                }
+
+                engine
+                    .start_recording(Path::new(&directory))
+                    .or_else(|e| cx.throw_error(format!("Failed to start recording: {e}")))?;
+                Ok(cx.undefined().as_value(cx))
+            })
+        })
+    }),
+    ("getLatency", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let latency = engine.latency();
+
+                let latency_js = cx.empty_object();
+                let input_js = cx.number(latency.input as f64);
+                latency_js.set(cx, "input", input_js)?;
+                let output_js = cx.number(latency.output as f64);
+                latency_js.set(cx, "output", output_js)?;
+                let processing_js = cx.number(latency.processing as f64);
+                latency_js.set(cx, "processing", processing_js)?;
+                Ok(latency_js.as_value(cx))
+            })
+        })
+    }),
+    ("getStats", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                Ok(construct_stats(cx, &engine.stats())?.as_value(cx))
+            })
+        })
+    }),
+    ("onXrun", |mut cx| {
+        let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+        let interval = device_watcher::interval_option(&mut cx, 1)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            let mut previous = shared_engine.with_inner(cx, |_, engine| Ok(engine.stats()))?;
+            let shared_engine = shared_engine.clone();
+
+            let watcher = device_watcher::spawn(
+                cx,
+                interval,
+                move || {
+                    // Stop watching once the engine is closed.
+                    let stats = shared_engine.try_with_inner(|engine| engine.stats())?;
+                    // A count lower than before means the stats were reset, e.g. by a restart of the stream,
+                    // so everything counted since then is new.
+                    let underruns = stats
+                        .underruns
+                        .checked_sub(previous.underruns)
+                        .unwrap_or(stats.underruns);
+                    let overruns = stats
+                        .overruns
+                        .checked_sub(previous.overruns)
+                        .unwrap_or(stats.overruns);
+                    previous = stats;
+
+                    if underruns == 0 && overruns == 0 {
+                        Some(Vec::new())
+                    } else {
+                        Some(vec![(underruns, overruns, stats)])
+                    }
+                },
+                move |cx, xruns| {
+                    for (underruns, overruns, stats) in xruns {
+                        let event_js = cx.empty_object();
+                        let underruns_js = cx.number(underruns as f64);
+                        event_js.set(cx, "underruns", underruns_js)?;
+                        let overruns_js = cx.number(overruns as f64);
+                        event_js.set(cx, "overruns", overruns_js)?;
+                        let stats_js = construct_stats(cx, &stats)?;
+                        event_js.set(cx, "stats", stats_js)?;
+
+                        device_watcher::call(cx, &callback, event_js)?;
+                    }
+                    Ok(())
+                },
+            )?;
+            Ok(watcher.as_value(cx))
+        })
+    }),
+    ("isRecording", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                Ok(cx.boolean(engine.is_recording()).as_value(cx))
+            })
+        })
+    }),
+    ("stopRecording", |mut cx| {
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.with_inner(cx, |cx, engine| {
+                let recordings = engine
+                    .stop_recording()
+                    .or_else(|e| cx.throw_error(format!("Failed to stop recording: {e}")))?;
+
+                // The recorded audio arrives late by the round-trip latency,
+                // so that much is skipped at the start of the recording to line it up with the timeline.
+                let latency = engine.latency();
+                let compensation = adae::Timestamp::from_samples(
+                    latency.input + latency.output + latency.processing,
+                    engine.config().output_config.sample_rate,
+                    engine.bpm_cents(),
+                );
+
+                // Recorded audio takes the place of whatever was on the track in the recorded range.
+                // When loop recording, every pass is a take of the same clip, and the last one is active.
+                // Each recording is placed on its own, so one that fails doesn't keep the others off the timeline.
+                let mut clip_keys = Vec::with_capacity(recordings.len());
+                let mut removed = Vec::new();
+                let mut errors = Vec::new();
+                for recording in recordings {
+                    let source_offset = compensation;
+                    let length = match recording.length.checked_sub(source_offset) {
+                        Some(length) if length != adae::Timestamp::zero() => length,
+                        // Nothing was recorded after the latency has been skipped.
+                        _ => continue,
+                    };
+
+                    let placed = cx.try_catch(|cx| {
+                        let ((key, removed), _) =
+                            timeline_edit::atomic(cx, engine, |cx, engine, edit| {
+                                let room = overlap::make_room(
+                                    cx,
+                                    shared_engine,
+                                    engine,
+                                    edit,
+                                    recording.audio_track_key,
+                                    recording.start,
+                                    length,
+                                    None,
+                                    OverlapMode::Replace,
+                                )?;
+
+                                let timeline_track_key = engine
+                                    .audio_timeline_track_key(recording.audio_track_key)
+                                    .or_throw(cx)?;
+                                let last_take = match recording.takes.last() {
+                                    Some(&last_take) => last_take,
+                                    None => return cx.throw_error("Recording has no takes"),
+                                };
+                                let key = engine
+                                    .add_audio_clip(
+                                        timeline_track_key,
+                                        last_take,
+                                        recording.start,
+                                        Some(room.length),
+                                    )
+                                    .or_else(|e| {
+                                        cx.throw_error(format!("Failed to add recorded clip: {e}"))
+                                    })?;
+                                edit.record_created(key);
+                                engine
+                                    .audio_clip_set_source_offset(key, source_offset)
+                                    .or_else(|e| {
+                                        cx.throw_error(format!("Failed to add recorded clip: {e}"))
+                                    })?;
+                                engine
+                                    .audio_clip_set_takes(key, recording.takes.clone())
+                                    .or_else(|e| {
+                                        cx.throw_error(format!("Failed to add recorded clip: {e}"))
+                                    })?;
+
+                                let removed = room.finish(cx, engine, key)?;
+                                Ok((key, removed))
+                            })?;
+                        Ok((key, removed))
+                    });
+
+                    match placed {
+                        Ok((key, clip_removed)) => {
+                            clip_keys.push(key);
+                            removed.extend(clip_removed);
+                        }
+                        Err(error) => errors.push(error),
+                    }
+                }
+
+                let clips_js = JsArray::new(cx, clip_keys.len());
+                for (i, key) in clip_keys.into_iter().enumerate() {
+                    let clip_js = audio_clip::construct(cx, key, shared_engine.clone())?;
+                    clips_js.set(cx, i as u32, clip_js)?;
+                }
+                let removed_js = audio_clip::encapsulate_states(cx, removed)?;
+                let errors_js = JsArray::new(cx, errors.len());
+                for (i, error) in errors.into_iter().enumerate() {
+                    errors_js.set(cx, i as u32, error)?;
+                }
+
+                let result_js = cx.empty_object();
+                result_js.set(cx, "clips", clips_js)?;
+                result_js.set(cx, "removed", removed_js)?;
+                result_js.set(cx, "errors", errors_js)?;
+                Ok(result_js.as_value(cx))
+            })
+        })
+    }),
+    ("onOutputDeviceLost", |mut cx| {
+        let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+        let interval = device_watcher::interval_option(&mut cx, 1)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            shared_engine.assert_not_closed(cx)?;
+            let shared_engine = shared_engine.clone();
+            let mut was_present = true;
+
+            let watcher = device_watcher::spawn(
+                cx,
+                interval,
+                move || {
+                    // Stop watching once the engine is closed.
+                    let output_device = shared_engine
+                        .try_with_inner(|engine| engine.config().output_device.clone())?;
+                    let present = match device_watcher::output_device_present(&output_device) {
+                        Some(present) => present,
+                        // Don't report the device as lost just because the host could not be queried.
+                        None => return Some(Vec::new()),
+                    };
+
+                    let lost = was_present && !present;
+                    was_present = present;
+                    if lost {
+                        Some(vec![output_device.name().to_string()])
+                    } else {
+                        Some(Vec::new())
+                    }
+                },
+                move |cx, names| {
+                    for name in names {
+                        let name_js = cx.string(name);
+                        device_watcher::call(cx, &callback, name_js)?;
+                    }
+                    Ok(())
+                },
+            )?;
+            Ok(watcher.as_value(cx))
+        })
+    }),
+    ("enableFailover", |mut cx| {
+        let target_js = cx.argument::<JsString>(0)?;
+        let target = failover::get(&mut cx, target_js)?;
+        let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+        let interval = device_watcher::interval_option(&mut cx, 2)?;
+
+        unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
+            let mut config =
+                shared_engine.with_inner(cx, |_, engine| Ok(engine.config().clone()))?;
+            let poll_engine = shared_engine.clone();
+            let mut was_present = true;
+
+            let stopped = Arc::new(AtomicBool::new(false));
+            let watcher = device_watcher::spawn_with_stop_flag(
+                cx,
+                interval,
+                Arc::clone(&stopped),
+                move || {
+                    match poll_engine.try_with_inner(|engine| engine.config().clone()) {
+                        Some(current) => config = current,
+                        // Stop watching once the engine is closed.
+                        None if !poll_engine.is_poisoned() => return None,
+                        // The last known output device is still watched, so its loss is reported.
+                        None => {}
+                    }
+                    let present = match device_watcher::output_device_present(&config.output_device)
+                    {
+                        Some(present) => present,
+                        // Don't fail over just because the host could not be queried.
+                        None => return Some(Vec::new()),
+                    };
+
+                    // Only fail over once per loss, even if the switch fails.
+                    let lost = was_present && !present;
+                    was_present = present;
+                    if !lost {
+                        return Some(Vec::new());
+                    }
+
+                    // The devices are queried before locking the engine,
+                    // so only the switch itself holds the lock, and JavaScript calls aren't blocked for longer.
+                    let default_config = match target {
+                        FailoverTarget::DefaultOutputDevice => {
+                            failover::default_config(config.input.clone(), config.thread.clone())
+                        }
+                        FailoverTarget::Dummy => None,
+                    };
+                    let lost_output_device = config.output_device.name().to_string();
+                    let failover = poll_engine.try_with_inner(|engine| {
+                        // The config might have been changed while the devices were queried.
+                        if engine.config().output_device.name() != lost_output_device {
+                            return None;
+                        }
+                        Some(failover::fail_over(
+                            engine,
+                            lost_output_device.clone(),
+                            default_config,
+                        ))
+                    });
+                    let failover = match failover {
+                        Some(failover) => failover,
+                        None if poll_engine.is_poisoned() => {
+                            Some(failover::poisoned(lost_output_device))
+                        }
+                        None => return None,
+                    };
+                    Some(failover.into_iter().collect())
+                },
+                move |cx, failovers| {
+                    for failover in failovers {
+                        let failover_js = failover::construct(cx, failover)?;
+                        device_watcher::call(cx, &callback, failover_js)?;
+                    }
+                    Ok(())
+                },
+            )?;
+            shared_engine.replace_failover_watcher(stopped);
+            Ok(watcher.as_value(cx))
+        })
+    }),
     ("close", |mut cx| {
         unpack_this(&mut cx, |cx, shared_engine: &SharedEngine| {
             shared_engine.close();
@@ -308,6 +989,91 @@ const METHODS: &[(&str, Method)] = &[
     }),
 ];
 
+/// Read the `tracks` property of an optional options object at the given argument index.
+///
+/// Returns `None` if either the object or the property is left out, `undefined` or `null`.
+fn tracks_option(
+    cx: &mut FunctionContext<'_>,
+    i: usize,
+) -> NeonResult<Option<Vec<adae::AudioTrackKey>>> {
+    let options_js = match cx.argument_opt(i) {
+        Some(val) if !is_nullish(cx, val) => val.downcast_or_throw::<JsObject, _>(cx)?,
+        _ => return Ok(None),
+    };
+
+    let tracks_js: Handle<JsValue> = options_js.get(cx, "tracks")?;
+    if is_nullish(cx, tracks_js) {
+        return Ok(None);
+    }
+    let tracks_js_array = tracks_js.downcast_or_throw::<JsArray, _>(cx)?;
+    let audio_track_keys = audio_track::unpack_audio_track_keys(cx, tracks_js_array)?;
+
+    Ok(Some(audio_track_keys))
+}
+
+/// Read a range of time from the arguments at index `i` and `i + 1`.
+///
+/// The end must not come before the start, but they may be equal, making the range empty.
+fn range_arguments(
+    cx: &mut FunctionContext<'_>,
+    i: usize,
+) -> NeonResult<(adae::Timestamp, adae::Timestamp)> {
+    let start_js: Handle<JsObject> = cx.argument(i)?;
+    let start = timestamp(cx, start_js)?;
+    let end_js: Handle<JsObject> = cx.argument(i + 1)?;
+    let end = timestamp(cx, end_js)?;
+
+    if end < start {
+        return cx.throw_range_error(format!(
+            "End of range must not be before its start: {start:?} > {end:?}"
+        ));
+    }
+
+    Ok((start, end))
+}
+
+/// The returned object must adhere to the `TimeRange` interface defined in the `index.d.ts` file, or be `null`.
+fn construct_range_opt<'a, C>(
+    cx: &mut C,
+    range: Option<(adae::Timestamp, adae::Timestamp)>,
+) -> JsResult<'a, JsValue>
+where
+    C: Context<'a>,
+{
+    let Some((start, end)) = range else {
+        return Ok(cx.null().as_value(cx));
+    };
+
+    let range_js = cx.empty_object();
+    let start_js = timestamp::construct(cx, start)?;
+    range_js.set(cx, "start", start_js)?;
+    let end_js = timestamp::construct(cx, end)?;
+    range_js.set(cx, "end", end_js)?;
+    Ok(range_js.as_value(cx))
+}
+
+/// The returned object must adhere to the `Stats` interface defined in the `index.d.ts` file.
+fn construct_stats<'a, C>(cx: &mut C, stats: &adae::Stats) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let stats_js = cx.empty_object();
+
+    // Loads are reported by the engine as fractions of the buffer time.
+    let average_load_js = cx.number(stats.average_load * 100.0);
+    stats_js.set(cx, "averageLoad", average_load_js)?;
+    let peak_load_js = cx.number(stats.peak_load * 100.0);
+    stats_js.set(cx, "peakLoad", peak_load_js)?;
+    let underruns_js = cx.number(stats.underruns as f64);
+    stats_js.set(cx, "underruns", underruns_js)?;
+    let overruns_js = cx.number(stats.overruns as f64);
+    stats_js.set(cx, "overruns", overruns_js)?;
+    let buffer_size_js = cx.number(stats.buffer_size as f64);
+    stats_js.set(cx, "bufferSize", buffer_size_js)?;
+
+    Ok(stats_js)
+}
+
 fn meter_scale(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let value_js: Handle<JsNumber> = cx.argument(0)?;
     let value = value_js.value(&mut cx) as f32;
@@ -0,0 +1,186 @@
+use neon::prelude::*;
+
+use adae::Timestamp;
+
+use crate::encapsulator::{self, encapsulate, Method};
+use crate::timestamp::{self, beat_units_per_beat, timestamp};
+
+pub fn class<'a, C>(cx: &mut C) -> JsResult<'a, JsValue>
+where
+    C: Context<'a>,
+{
+    let class = JsFunction::new(cx, |mut cx| {
+        cx.throw_error::<_, Handle<JsValue>>(
+            "Offset cannot be constructed directly. Use the static methods instead.",
+        )
+    })?;
+    for (name, method) in STATIC_METHODS {
+        let method_js = JsFunction::new(cx, *method)?;
+        class.set(cx, *name, method_js)?;
+    }
+    Ok(class.as_value(cx))
+}
+
+/// `beat_units` must have a magnitude smaller than 2^32.
+pub fn construct<'a, C>(cx: &mut C, beat_units: i64) -> JsResult<'a, JsValue>
+where
+    C: Context<'a>,
+{
+    Ok(encapsulate(cx, OffsetWrapper(beat_units), &[], METHODS)?.as_value(cx))
+}
+
+/// Get the offset in beat units.
+pub fn offset(cx: &mut FunctionContext<'_>, obj: Handle<JsObject>) -> NeonResult<i64> {
+    let boxed: Handle<JsBox<OffsetWrapper>> = obj.get(cx, encapsulator::DATA_KEY)?;
+    let wrapper = &*boxed;
+    Ok(wrapper.0)
+}
+
+/// Apply `offset` to `timestamp`.
+///
+/// Returns `None` if the result would be before zero or past the end of the timeline.
+pub fn apply(timestamp: Timestamp, offset: i64) -> Option<Timestamp> {
+    let beat_units = timestamp.beat_units() as i64 + offset;
+    let beat_units = u32::try_from(beat_units).ok()?;
+    Some(Timestamp::from_beat_units(beat_units))
+}
+
+/// Apply `offset` to `timestamp`, clamping the result to the range from zero to [`Timestamp::infinity()`].
+pub fn apply_clamped(timestamp: Timestamp, offset: i64) -> Timestamp {
+    let beat_units = timestamp.beat_units() as i64 + offset;
+    let beat_units = beat_units.clamp(0, Timestamp::infinity().beat_units() as i64);
+    Timestamp::from_beat_units(beat_units as u32)
+}
+
+struct OffsetWrapper(i64);
+impl Finalize for OffsetWrapper {}
+
+/// Example: `err_msg("beat", "smaller than 2^22", 1e9)` -> `"Offset must have beat value smaller than 2^22. Got 1000000000"`
+fn err_msg(property: &str, expected_to_be: &str, value: f64) -> String {
+    format!("Offset must have {property} value {expected_to_be}. Got {value}")
+}
+
+/// The largest magnitude an offset can have, which allows it to span the entire timeline in either direction.
+const MAX_BEAT_UNITS: i64 = u32::MAX as i64;
+
+/// Construct an offset, or throw a `RangeError` if its magnitude is too large.
+fn construct_checked<'a>(cx: &mut FunctionContext<'a>, beat_units: i64) -> JsResult<'a, JsValue> {
+    if !(-MAX_BEAT_UNITS..=MAX_BEAT_UNITS).contains(&beat_units) {
+        return cx.throw_range_error(err_msg(
+            "beat unit",
+            "with a magnitude smaller than 2^32",
+            beat_units as f64,
+        ));
+    }
+    construct(cx, beat_units)
+}
+
+const STATIC_METHODS: &[(&str, Method)] = &[
+    ("zero", |mut cx| construct(&mut cx, 0)),
+    ("fromBeatUnits", |mut cx| {
+        let beat_units_js: Handle<JsNumber> = cx.argument(0)?;
+        let beat_units_f64 = beat_units_js.value(&mut cx).trunc();
+
+        if (MAX_BEAT_UNITS as f64) < beat_units_f64.abs() {
+            return cx.throw_range_error(err_msg(
+                "beat unit",
+                "with a magnitude smaller than 2^32",
+                beat_units_f64,
+            ));
+        }
+
+        construct(&mut cx, beat_units_f64 as i64)
+    }),
+    ("fromBeats", |mut cx| {
+        let beats_js: Handle<JsNumber> = cx.argument(0)?;
+        let beats_f64 = beats_js.value(&mut cx);
+
+        let beat_units_f64 = (beats_f64 * beat_units_per_beat()).round();
+        if (MAX_BEAT_UNITS as f64) < beat_units_f64.abs() {
+            return cx.throw_range_error(err_msg(
+                "beat",
+                "with a magnitude smaller than 2^22",
+                beats_f64,
+            ));
+        }
+
+        construct(&mut cx, beat_units_f64 as i64)
+    }),
+    ("between", |mut cx| {
+        let from_js = cx.argument::<JsObject>(0)?;
+        let from = timestamp(&mut cx, from_js)?;
+
+        let to_js = cx.argument::<JsObject>(1)?;
+        let to = timestamp(&mut cx, to_js)?;
+
+        construct(&mut cx, to.beat_units() as i64 - from.beat_units() as i64)
+    }),
+    ("neg", |mut cx| {
+        let o_js = cx.argument::<JsObject>(0)?;
+        let o = offset(&mut cx, o_js)?;
+
+        construct(&mut cx, -o)
+    }),
+    ("add", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = offset(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = offset(&mut cx, b_js)?;
+
+        construct_checked(&mut cx, a + b)
+    }),
+    ("eq", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = offset(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = offset(&mut cx, b_js)?;
+
+        Ok(cx.boolean(a == b).as_value(&mut cx))
+    }),
+    ("apply", |mut cx| {
+        let ts_js = cx.argument::<JsObject>(0)?;
+        let ts = timestamp(&mut cx, ts_js)?;
+
+        let o_js = cx.argument::<JsObject>(1)?;
+        let o = offset(&mut cx, o_js)?;
+
+        let clamp = match cx.argument_opt(2) {
+            Some(val) if !val.is_a::<JsUndefined, _>(&mut cx) => {
+                let clamp_js = val.downcast_or_throw::<JsBoolean, _>(&mut cx)?;
+                clamp_js.value(&mut cx)
+            }
+            _ => false,
+        };
+
+        let r = if clamp {
+            apply_clamped(ts, o)
+        } else {
+            match apply(ts, o) {
+                Some(r) => r,
+                None => {
+                    return cx.throw_range_error(format!(
+                        "Offset would move timestamp outside the timeline: {ts:?} + {o} beat units"
+                    ))
+                }
+            }
+        };
+
+        timestamp::construct(&mut cx, r)
+    }),
+];
+
+const METHODS: &[(&str, Method)] = &[
+    ("getBeatUnits", |mut cx| {
+        let this = cx.this()?;
+        let offset = offset(&mut cx, this)?;
+        Ok(cx.number(offset as f64).as_value(&mut cx))
+    }),
+    ("getBeats", |mut cx| {
+        let this = cx.this()?;
+        let offset = offset(&mut cx, this)?;
+        let beats = offset as f64 / beat_units_per_beat();
+        Ok(cx.number(beats).as_value(&mut cx))
+    }),
+];
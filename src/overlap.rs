@@ -0,0 +1,335 @@
+use neon::prelude::*;
+
+use adae::Timestamp;
+
+use crate::clip::audio_clip;
+use crate::shared_engine::SharedEngine;
+use crate::timeline_edit::TimelineEditStateWrapper;
+use crate::timestamp::difference;
+use crate::utils::ResultExt;
+
+/// How to handle a clip being placed on top of other clips.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverlapMode {
+    /// Throw an `OverlapError` naming the clips in the way.
+    Reject,
+    /// Crop the clips in the way, without deleting any of them.
+    ///
+    /// A clip that sticks out at both ends is split where the placed clip ends, and the part before that is cropped.
+    /// Clips that are entirely covered can't be cropped, so they are rejected like in [`OverlapMode::Reject`].
+    Trim,
+    /// Delete the parts of the clips that are covered.
+    Replace,
+    /// Trim the clips in the way like [`OverlapMode::Trim`],
+    /// and crossfade the clip with the neighbours it overlapped.
+    Crossfade,
+}
+
+pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let obj = cx.empty_object();
+
+    let fields = [
+        ("Reject", "reject"),
+        ("Trim", "trim"),
+        ("Replace", "replace"),
+        ("Crossfade", "crossfade"),
+    ];
+
+    for (name, val) in fields.iter() {
+        let str = cx.string(*val);
+        obj.set(cx, *name, str)?;
+    }
+
+    Ok(obj)
+}
+
+pub fn get<'a, C>(cx: &mut C, str: Handle<'a, JsString>) -> NeonResult<OverlapMode>
+where
+    C: Context<'a>,
+{
+    let overlap_mode = match str.value(cx).as_str() {
+        "reject" => OverlapMode::Reject,
+        "trim" => OverlapMode::Trim,
+        "replace" => OverlapMode::Replace,
+        "crossfade" => OverlapMode::Crossfade,
+        _ => return cx.throw_error(format!("Invalid overlap mode: {str:?}")),
+    };
+
+    Ok(overlap_mode)
+}
+
+/// Read an optional overlap mode from the argument at index `i`.
+///
+/// Returns `None` if the argument is left out or `undefined`.
+pub fn argument_opt(cx: &mut FunctionContext<'_>, i: usize) -> NeonResult<Option<OverlapMode>> {
+    match cx.argument_opt(i) {
+        Some(val) if !val.is_a::<JsUndefined, _>(cx) => {
+            let overlap_mode_js = val.downcast_or_throw::<JsString, _>(cx)?;
+            Ok(Some(get(cx, overlap_mode_js)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The result of [`make_room`].
+pub struct Room {
+    /// The length that the clip must be placed with.
+    ///
+    /// This is only shorter than the requested length in [`OverlapMode::Crossfade`],
+    /// where the clip is extended to its full length again by [`Room::finish`].
+    pub length: Timestamp,
+    removed: Vec<adae::AudioClipState>,
+    crossfades: Vec<(adae::AudioClipKey, Side, Timestamp)>,
+}
+impl Room {
+    /// Apply any crossfades between the placed clip and the clips that were trimmed to make room for it.
+    ///
+    /// Returns the states of the clips or parts of clips that were deleted to make room.
+    pub fn finish<'a>(
+        self,
+        cx: &mut FunctionContext<'a>,
+        engine: &mut adae::Engine,
+        clip_key: adae::AudioClipKey,
+    ) -> NeonResult<Vec<adae::AudioClipState>> {
+        for (neighbour_key, side, length) in self.crossfades {
+            let fade = adae::Fade {
+                length,
+                curve: adae::FadeCurve::EqualPower,
+            };
+            let (left, right) = match side {
+                Side::Before => (neighbour_key, clip_key),
+                Side::After => (clip_key, neighbour_key),
+            };
+            engine
+                .audio_clip_crossfade(left, right, fade)
+                .or_else(|e| cx.throw_error(format!("Failed to crossfade clips: {e}")))?;
+        }
+
+        Ok(self.removed)
+    }
+}
+
+/// Which side of the placed clip a neighbouring clip is on.
+enum Side {
+    Before,
+    After,
+}
+
+/// Make room for a clip of the given `length` at `start` on the audio track, according to `mode`.
+///
+/// The clip with the key `ignored` is left untouched, which allows a clip to be moved on top of itself.
+/// Every clip that is changed, created or deleted is recorded in `edit`,
+/// so this should be run as part of [`atomic`](crate::timeline_edit::atomic) along with placing the clip.
+///
+/// The clip must then be placed with the length given by [`Room::length`], after which [`Room::finish`] must be called.
+#[allow(clippy::too_many_arguments)]
+pub fn make_room<'a>(
+    cx: &mut FunctionContext<'a>,
+    shared_engine: &SharedEngine,
+    engine: &mut adae::Engine,
+    edit: &mut TimelineEditStateWrapper,
+    audio_track_key: adae::AudioTrackKey,
+    start: Timestamp,
+    length: Timestamp,
+    ignored: Option<adae::AudioClipKey>,
+    mode: OverlapMode,
+) -> NeonResult<Room> {
+    let bpm_cents = engine.bpm_cents();
+    let end = start.checked_add(length).unwrap_or(Timestamp::infinity());
+
+    let timeline_track_key = engine
+        .audio_timeline_track_key(audio_track_key)
+        .or_throw(cx)?;
+    let conflicts: Vec<(adae::AudioClipKey, Timestamp, Timestamp)> = engine
+        .audio_clips(timeline_track_key)
+        .or_throw(cx)?
+        .filter(|clip| Some(clip.key) != ignored)
+        .map(|clip| {
+            let clip_start = clip.start();
+            let clip_end = clip_start
+                .checked_add(clip.length(bpm_cents))
+                .unwrap_or(Timestamp::infinity());
+            (clip.key, clip_start, clip_end)
+        })
+        .filter(|&(_, clip_start, clip_end)| clip_start < end && start < clip_end)
+        .collect();
+
+    let mut room = Room {
+        length,
+        removed: Vec::new(),
+        crossfades: Vec::new(),
+    };
+
+    if conflicts.is_empty() {
+        return Ok(room);
+    }
+
+    if mode == OverlapMode::Reject {
+        let clip_keys: Vec<_> = conflicts.iter().map(|&(clip_key, _, _)| clip_key).collect();
+        return throw_overlap_error(cx, shared_engine, &clip_keys);
+    }
+    if mode != OverlapMode::Replace {
+        let covered_keys: Vec<_> = conflicts
+            .iter()
+            .filter(|&&(_, clip_start, clip_end)| start <= clip_start && clip_end <= end)
+            .map(|&(clip_key, _, _)| clip_key)
+            .collect();
+        if !covered_keys.is_empty() {
+            return throw_overlap_error(cx, shared_engine, &covered_keys);
+        }
+    }
+
+    // Crossfades are capped, so there is room for one at each end of the clip.
+    let max_crossfade = Timestamp::from_beat_units(length.beat_units() / 2);
+    let mut placed_end = end;
+
+    for (clip_key, clip_start, clip_end) in conflicts {
+        edit.record(cx, engine, audio_track_key, clip_key)?;
+
+        let covers_start = start <= clip_start;
+        let covers_end = clip_end <= end;
+
+        match (covers_start, covers_end) {
+            // Entirely covered, which only happens in `OverlapMode::Replace`
+            (true, true) => {
+                room.removed.push(delete(cx, engine, edit, clip_key)?);
+            }
+
+            // Sticks out before the start
+            (false, true) => match mode {
+                OverlapMode::Replace => {
+                    let covered_key = split(cx, engine, edit, clip_key, start)?;
+                    room.removed.push(delete(cx, engine, edit, covered_key)?);
+                }
+                _ => {
+                    let new_length = difference(cx, start, clip_start)?;
+                    engine
+                        .audio_clip_crop_end(clip_key, new_length)
+                        .or_throw(cx)?;
+
+                    if mode == OverlapMode::Crossfade {
+                        let overlap =
+                            Timestamp::min(difference(cx, clip_end, start)?, max_crossfade);
+                        room.crossfades.push((clip_key, Side::Before, overlap));
+                    }
+                }
+            },
+
+            // Sticks out after the end
+            (true, false) => match mode {
+                OverlapMode::Replace => {
+                    split(cx, engine, edit, clip_key, end)?;
+                    room.removed.push(delete(cx, engine, edit, clip_key)?);
+                }
+                _ => {
+                    let overlap = match mode {
+                        OverlapMode::Crossfade => {
+                            Timestamp::min(difference(cx, end, clip_start)?, max_crossfade)
+                        }
+                        _ => Timestamp::zero(),
+                    };
+                    let new_start = difference(cx, end, overlap)?;
+                    engine
+                        .audio_clip_crop_start(clip_key, difference(cx, clip_end, new_start)?)
+                        .or_throw(cx)?;
+
+                    if mode == OverlapMode::Crossfade {
+                        placed_end = Timestamp::min(placed_end, new_start);
+                        room.crossfades.push((clip_key, Side::After, overlap));
+                    }
+                }
+            },
+
+            // Sticks out at both ends
+            (false, false) => {
+                // The rest is left under the end of the clip when crossfading, so it has something to crossfade with.
+                let rest_start = match mode {
+                    OverlapMode::Crossfade => difference(cx, end, max_crossfade)?,
+                    _ => end,
+                };
+                let rest_key = split(cx, engine, edit, clip_key, rest_start)?;
+
+                match mode {
+                    // The covered part is cut out in one piece, so it can be reconstructed.
+                    OverlapMode::Replace => {
+                        let covered_key = split(cx, engine, edit, clip_key, start)?;
+                        room.removed.push(delete(cx, engine, edit, covered_key)?);
+                    }
+                    _ => {
+                        engine
+                            .audio_clip_crop_end(clip_key, difference(cx, start, clip_start)?)
+                            .or_throw(cx)?;
+                    }
+                }
+
+                if mode == OverlapMode::Crossfade {
+                    placed_end = Timestamp::min(placed_end, rest_start);
+                    room.crossfades
+                        .push((clip_key, Side::Before, max_crossfade));
+                    room.crossfades.push((rest_key, Side::After, max_crossfade));
+                }
+            }
+        }
+    }
+
+    room.length = difference(cx, placed_end, start)?;
+    Ok(room)
+}
+
+/// Throw an `OverlapError` naming the given clips, which must adhere to the `OverlapError` interface defined in the `index.d.ts` file.
+fn throw_overlap_error<'a, T>(
+    cx: &mut FunctionContext<'a>,
+    shared_engine: &SharedEngine,
+    clip_keys: &[adae::AudioClipKey],
+) -> NeonResult<T> {
+    let keys: Vec<String> = clip_keys
+        .iter()
+        .map(|&clip_key| {
+            let key: u32 = clip_key.into();
+            key.to_string()
+        })
+        .collect();
+
+    let clips_js = JsArray::new(cx, clip_keys.len());
+    for (i, &clip_key) in clip_keys.iter().enumerate() {
+        let clip_js = audio_clip::construct(cx, clip_key, shared_engine.clone())?;
+        clips_js.set(cx, i as u32, clip_js)?;
+    }
+
+    let error = cx.error(format!(
+        "Clip would overlap existing clips with keys: {}",
+        keys.join(", ")
+    ))?;
+    let name = cx.string("OverlapError");
+    error.set(cx, "name", name)?;
+    error.set(cx, "clips", clips_js)?;
+    cx.throw(error)
+}
+
+/// Split the clip at `at`, and record the new part after `at` in `edit`.
+fn split<'a>(
+    cx: &mut FunctionContext<'a>,
+    engine: &mut adae::Engine,
+    edit: &mut TimelineEditStateWrapper,
+    clip_key: adae::AudioClipKey,
+    at: Timestamp,
+) -> NeonResult<adae::AudioClipKey> {
+    let right_key = engine.audio_clip_split(clip_key, at).or_throw(cx)?;
+    edit.record_created(right_key);
+    Ok(right_key)
+}
+
+/// Delete the clip, and record it in `edit`.
+fn delete<'a>(
+    cx: &mut FunctionContext<'a>,
+    engine: &mut adae::Engine,
+    edit: &mut TimelineEditStateWrapper,
+    clip_key: adae::AudioClipKey,
+) -> NeonResult<adae::AudioClipState> {
+    let state = engine.delete_audio_clip(clip_key).or_throw(cx)?;
+    edit.record_deleted(clip_key);
+    Ok(state)
+}
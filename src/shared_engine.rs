@@ -1,5 +1,6 @@
 use std::fmt::Debug;
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 use neon::{context::Context, result::Throw, types::Finalize};
 
@@ -8,10 +9,12 @@ pub struct SharedEngine(
     // Mutex allows the value to be borrowed mutably from one place at a time
     // Option allows the engine to be dropped which stops audio
     Arc<Mutex<Option<adae::Engine>>>,
+    // The stop flag of the watcher started by `Engine.enableFailover()`, so there is only one per engine
+    Arc<Mutex<Option<Arc<AtomicBool>>>>,
 );
 impl SharedEngine {
     pub fn empty() -> Self {
-        Self(Arc::new(Mutex::new(Some(adae::Engine::empty()))))
+        Self::from_engine(adae::Engine::empty())
     }
 
     pub fn new(
@@ -21,11 +24,25 @@ impl SharedEngine {
         adae::error::InvalidConfigError,
     > {
         let (engine, import_errors) = adae::Engine::new(config, &adae::EngineState::default())?;
-        Ok((Self(Arc::new(Mutex::new(Some(engine)))), import_errors))
+        Ok((Self::from_engine(engine), import_errors))
     }
 
     pub fn dummy() -> Self {
-        Self(Arc::new(Mutex::new(Some(adae::Engine::dummy()))))
+        Self::from_engine(adae::Engine::dummy())
+    }
+
+    /// A dummy engine with a dummy input device, which records silence.
+    pub fn dummy_with_input() -> Result<Self, adae::error::InvalidConfigError> {
+        let config = adae::config::Config {
+            input: Some(adae::config::Input::dummy()),
+            ..adae::config::Config::dummy()
+        };
+        let (engine, _) = Self::new(config)?;
+        Ok(engine)
+    }
+
+    fn from_engine(engine: adae::Engine) -> Self {
+        Self(Arc::new(Mutex::new(Some(engine))), Arc::default())
     }
 
     fn lock<'a, C>(&self, cx: &mut C) -> Result<MutexGuard<Option<adae::Engine>>, Throw>
@@ -54,6 +71,20 @@ impl SharedEngine {
         callback(cx, engine)
     }
 
+    /// Call the given callback with a mutable reference to the engine, from outside of a JavaScript context.
+    ///
+    /// Returns `None` if the engine has been closed, or if a panic has occurred while holding a lock on it.
+    /// Use [`SharedEngine::is_poisoned`] to tell these apart.
+    pub fn try_with_inner<R, F>(&self, callback: F) -> Option<R>
+    where
+        F: FnOnce(&mut adae::Engine) -> R,
+    {
+        let mut option_guard = self.0.lock().ok()?;
+        let engine = option_guard.as_mut()?;
+
+        Some(callback(engine))
+    }
+
     /// Throws an error if the engine has been closed.
     pub fn assert_not_closed<'a, C>(&self, cx: &mut C) -> Result<(), Throw>
     where
@@ -66,6 +97,20 @@ impl SharedEngine {
         }
     }
 
+    /// Whether a panic has occurred while holding a lock on the engine, so it can't be used anymore.
+    pub fn is_poisoned(&self) -> bool {
+        self.0.is_poisoned()
+    }
+
+    /// Use `stopped` as the stop flag of the engine's failover watcher, and stop the previous one.
+    pub fn replace_failover_watcher(&self, stopped: Arc<AtomicBool>) {
+        // The slot only holds a flag, which can't be left in an inconsistent state by a panic.
+        let mut slot = self.1.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(previous) = slot.replace(stopped) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
     pub fn close(&self) {
         let lock_result = self.0.lock();
         if let Ok(mut option) = lock_result {
@@ -77,7 +122,7 @@ impl SharedEngine {
 impl Clone for SharedEngine {
     /// Clones engine via Arc.
     fn clone(&self) -> Self {
-        Self(Arc::clone(&self.0))
+        Self(Arc::clone(&self.0), Arc::clone(&self.1))
     }
 }
 impl Debug for SharedEngine {
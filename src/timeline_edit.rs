@@ -0,0 +1,341 @@
+use neon::prelude::*;
+
+use adae::Timestamp;
+
+use crate::timestamp::difference;
+use crate::utils::ResultExt;
+
+/// The state needed to revert an edit spanning multiple clips and tracks.
+#[derive(Debug, Default)]
+pub struct TimelineEditStateWrapper {
+    /// The clips as they were before the edit, along with the audio tracks they were on.
+    original: Vec<(
+        adae::AudioTrackKey,
+        adae::AudioClipKey,
+        adae::AudioClipState,
+    )>,
+    /// The clips resulting from the edit, which replace the original ones.
+    result: Vec<adae::AudioClipKey>,
+}
+impl TimelineEditStateWrapper {
+    /// Record the state of the clip on the audio track before it is changed by the edit,
+    /// unless it has been recorded already.
+    pub fn record<'a, C>(
+        &mut self,
+        cx: &mut C,
+        engine: &adae::Engine,
+        audio_track_key: adae::AudioTrackKey,
+        clip_key: adae::AudioClipKey,
+    ) -> NeonResult<()>
+    where
+        C: Context<'a>,
+    {
+        if self.original.iter().any(|&(_, key, _)| key == clip_key) {
+            return Ok(());
+        }
+
+        let clip_state = engine.audio_clip(clip_key).or_throw(cx)?.state();
+        self.original.push((audio_track_key, clip_key, clip_state));
+        self.result.push(clip_key);
+        Ok(())
+    }
+
+    /// Record a clip that was created by the edit, e.g. by adding or splitting a clip.
+    pub fn record_created(&mut self, clip_key: adae::AudioClipKey) {
+        self.result.push(clip_key);
+    }
+
+    /// Record that a clip was deleted by the edit.
+    pub fn record_deleted(&mut self, clip_key: adae::AudioClipKey) {
+        self.result.retain(|&key| key != clip_key);
+    }
+
+    /// Undo an edit that failed partway through, placing the clips as they were before it.
+    ///
+    /// An error has already been thrown at this point, so any further errors are ignored.
+    fn roll_back(&mut self, engine: &mut adae::Engine) {
+        for clip_key in self.result.drain(..) {
+            let _ = engine.delete_audio_clip(clip_key);
+        }
+        for (audio_track_key, _, clip_state) in self.original.drain(..) {
+            if let Ok(timeline_track_key) = engine.audio_timeline_track_key(audio_track_key) {
+                let _ = engine.reconstruct_audio_clip(timeline_track_key, clip_state);
+            }
+        }
+    }
+}
+impl Finalize for TimelineEditStateWrapper {}
+
+/// Run `edit`, which must record every clip it changes, creates or deletes in the given state.
+///
+/// If `edit` throws, everything it recorded is rolled back, so the timeline is left as it was.
+/// Otherwise, its result is returned along with the state needed to revert it.
+pub fn atomic<'a, C, T, F>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    edit: F,
+) -> NeonResult<(T, TimelineEditStateWrapper)>
+where
+    C: Context<'a>,
+    F: FnOnce(&mut C, &mut adae::Engine, &mut TimelineEditStateWrapper) -> NeonResult<T>,
+{
+    let mut state = TimelineEditStateWrapper::default();
+    match edit(cx, engine, &mut state) {
+        Ok(result) => Ok((result, state)),
+        Err(throw) => {
+            state.roll_back(engine);
+            Err(throw)
+        }
+    }
+}
+
+/// Insert `length` of empty time at `at` on the given audio tracks.
+///
+/// Clips after `at` are moved later, and clips that straddle `at` are split.
+/// If this fails on any track, all tracks are left as they were.
+pub fn insert_time<'a, C>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    audio_track_keys: &[adae::AudioTrackKey],
+    at: Timestamp,
+    length: Timestamp,
+) -> NeonResult<TimelineEditStateWrapper>
+where
+    C: Context<'a>,
+{
+    if length == Timestamp::zero() {
+        return Ok(TimelineEditStateWrapper::default());
+    }
+
+    let ((), state) = atomic(cx, engine, |cx, engine, state| {
+        for &audio_track_key in audio_track_keys {
+            let clips = affected_clips(
+                cx,
+                engine,
+                state,
+                audio_track_key,
+                at,
+                Timestamp::infinity(),
+            )?;
+
+            let mut moves = Vec::new();
+            for (clip_key, clip_start, _) in clips {
+                if clip_start < at {
+                    let right_key = engine.audio_clip_split(clip_key, at).or_throw(cx)?;
+                    state.record_created(right_key);
+                    moves.push((right_key, at));
+                } else {
+                    moves.push((clip_key, clip_start));
+                }
+            }
+
+            // Move the last clips first, so they don't collide with the ones behind them.
+            moves.sort_by_key(|&(_, clip_start)| clip_start);
+            for (clip_key, clip_start) in moves.into_iter().rev() {
+                let new_start = match clip_start.checked_add(length) {
+                    Some(new_start) => new_start,
+                    None => {
+                        return cx.throw_range_error(format!(
+                            "Clip cannot be moved past the end of the timeline: {clip_start:?} + {length:?}"
+                        ))
+                    }
+                };
+                engine
+                    .audio_clip_move(clip_key, new_start)
+                    .or_else(|e| cx.throw_error(format!("Failed to move clip: {e}")))?;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    Ok(state)
+}
+
+/// Remove the time from `start` to `end` on the given audio tracks.
+///
+/// Clips within the range are deleted, clips that straddle either end are cropped,
+/// and clips after the range are moved earlier to close the gap.
+/// If this fails on any track, all tracks are left as they were.
+pub fn remove_time<'a, C>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    audio_track_keys: &[adae::AudioTrackKey],
+    start: Timestamp,
+    end: Timestamp,
+) -> NeonResult<TimelineEditStateWrapper>
+where
+    C: Context<'a>,
+{
+    remove(cx, engine, audio_track_keys, start, end, true)
+}
+
+/// Clear the range from `start` to `end` on the given audio tracks, leaving a gap.
+///
+/// Clips within the range are deleted, and clips that straddle either end are cropped,
+/// while clips after the range stay in place.
+/// If this fails on any track, all tracks are left as they were.
+pub fn clear_time<'a, C>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    audio_track_keys: &[adae::AudioTrackKey],
+    start: Timestamp,
+    end: Timestamp,
+) -> NeonResult<TimelineEditStateWrapper>
+where
+    C: Context<'a>,
+{
+    remove(cx, engine, audio_track_keys, start, end, false)
+}
+
+fn remove<'a, C>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    audio_track_keys: &[adae::AudioTrackKey],
+    start: Timestamp,
+    end: Timestamp,
+    ripple: bool,
+) -> NeonResult<TimelineEditStateWrapper>
+where
+    C: Context<'a>,
+{
+    if start == end {
+        return Ok(TimelineEditStateWrapper::default());
+    }
+    let length = difference(cx, end, start)?;
+    // Without rippling, clips after the range are left untouched.
+    let until = if ripple { Timestamp::infinity() } else { end };
+
+    let ((), state) = atomic(cx, engine, |cx, engine, state| {
+        for &audio_track_key in audio_track_keys {
+            let clips = affected_clips(cx, engine, state, audio_track_key, start, until)?;
+
+            let mut moves = Vec::new();
+            for (clip_key, clip_start, clip_end) in clips {
+                match (clip_start < start, end < clip_end) {
+                    // Entirely within the range
+                    (false, false) => {
+                        engine.delete_audio_clip(clip_key).or_throw(cx)?;
+                        state.record_deleted(clip_key);
+                    }
+
+                    // Straddles the start
+                    (true, false) => {
+                        engine
+                            .audio_clip_crop_end(clip_key, difference(cx, start, clip_start)?)
+                            .or_throw(cx)?;
+                    }
+
+                    // Straddles both ends
+                    (true, true) => {
+                        let rest_key = engine.audio_clip_split(clip_key, end).or_throw(cx)?;
+                        state.record_created(rest_key);
+                        engine
+                            .audio_clip_crop_end(clip_key, difference(cx, start, clip_start)?)
+                            .or_throw(cx)?;
+                        if ripple {
+                            moves.push((rest_key, start));
+                        }
+                    }
+
+                    // Straddles the end, or lies after it
+                    (false, true) => {
+                        if clip_start < end {
+                            engine
+                                .audio_clip_crop_start(clip_key, difference(cx, clip_end, end)?)
+                                .or_throw(cx)?;
+                            if ripple {
+                                moves.push((clip_key, start));
+                            }
+                        } else {
+                            moves.push((clip_key, difference(cx, clip_start, length)?));
+                        }
+                    }
+                }
+            }
+
+            // Move the first clips first, so they don't collide with the ones in front of them.
+            moves.sort_by_key(|&(_, new_start)| new_start);
+            for (clip_key, new_start) in moves {
+                engine
+                    .audio_clip_move(clip_key, new_start)
+                    .or_else(|e| cx.throw_error(format!("Failed to move clip: {e}")))?;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    Ok(state)
+}
+
+/// Revert an edit, such that the clips are placed as they were before it.
+pub fn revert<'a, C>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    state: &TimelineEditStateWrapper,
+) -> NeonResult<()>
+where
+    C: Context<'a>,
+{
+    // Clips that have been deleted since the edit are already gone.
+    let result: Vec<_> = state
+        .result
+        .iter()
+        .copied()
+        .filter(|&clip_key| engine.audio_clip(clip_key).is_ok())
+        .collect();
+    engine
+        .delete_audio_clips(result)
+        .or_throw(cx)?
+        .for_each(drop);
+
+    for (audio_track_key, _, clip_state) in state.original.iter().cloned() {
+        let timeline_track_key = engine
+            .audio_timeline_track_key(audio_track_key)
+            .or_throw(cx)?;
+        engine
+            .reconstruct_audio_clip(timeline_track_key, clip_state)
+            .or_throw(cx)?;
+    }
+
+    Ok(())
+}
+
+/// Get the key, start and end of every clip on the audio track that ends after `from` and starts before `until`,
+/// and record their current state in `state`.
+fn affected_clips<'a, C>(
+    cx: &mut C,
+    engine: &mut adae::Engine,
+    state: &mut TimelineEditStateWrapper,
+    audio_track_key: adae::AudioTrackKey,
+    from: Timestamp,
+    until: Timestamp,
+) -> NeonResult<Vec<(adae::AudioClipKey, Timestamp, Timestamp)>>
+where
+    C: Context<'a>,
+{
+    let bpm_cents = engine.bpm_cents();
+    let timeline_track_key = engine
+        .audio_timeline_track_key(audio_track_key)
+        .or_throw(cx)?;
+
+    let clips: Vec<_> = engine
+        .audio_clips(timeline_track_key)
+        .or_throw(cx)?
+        .map(|clip| {
+            let clip_start = clip.start();
+            let clip_end = clip_start
+                .checked_add(clip.length(bpm_cents))
+                .unwrap_or(Timestamp::infinity());
+            (clip.key, clip_start, clip_end)
+        })
+        .filter(|&(_, clip_start, clip_end)| from < clip_end && clip_start < until)
+        .collect();
+
+    for &(clip_key, _, _) in &clips {
+        state.record(cx, engine, audio_track_key, clip_key)?;
+    }
+
+    Ok(clips)
+}
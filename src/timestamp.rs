@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use neon::prelude::*;
 
 use adae::Timestamp;
@@ -41,6 +43,212 @@ fn err_msg(property: &str, expected_to_be: &str, value: f64) -> String {
     format!("Timestamp must have {property} value {expected_to_be}. Got {value}")
 }
 
+/// The resolution of timestamps, taken from the engine so the two can't disagree.
+pub fn beat_units_per_beat() -> f64 {
+    Timestamp::from_beats(1).beat_units() as f64
+}
+
+/// `a - b`, e.g. the length between the start and end of a clip or range.
+///
+/// Throws a `RangeError` if `a` is earlier than `b`.
+pub fn difference<'a, C>(cx: &mut C, a: Timestamp, b: Timestamp) -> NeonResult<Timestamp>
+where
+    C: Context<'a>,
+{
+    match a.checked_sub(b) {
+        Some(difference) => Ok(difference),
+        None => cx.throw_range_error(format!(
+            "Timestamp cannot be subtracted from an earlier timestamp: {a:?} - {b:?}"
+        )),
+    }
+}
+
+/// Convert a number of beat units to a timestamp, truncating any fraction unless `whole` requires there to be none.
+fn from_beat_units<'a, C>(cx: &mut C, beat_units_f64: f64, whole: bool) -> NeonResult<Timestamp>
+where
+    C: Context<'a>,
+{
+    if beat_units_f64 < 0.0 {
+        return cx.throw_range_error(err_msg("beat unit", "greater than zero", beat_units_f64));
+    }
+    if (u32::MAX as f64) < beat_units_f64 {
+        return cx.throw_range_error(err_msg("beat unit", "smaller than 2^32", beat_units_f64));
+    }
+    if whole && beat_units_f64.fract() != 0.0 {
+        return cx.throw_range_error(err_msg("beat unit", "that is an integer", beat_units_f64));
+    }
+
+    Ok(Timestamp::from_beat_units(beat_units_f64 as u32))
+}
+
+/// Convert `seconds` to a timestamp at the given tempo, rounded to the nearest beat unit.
+pub fn from_seconds<'a, C>(cx: &mut C, seconds: f64, bpm_cents: u16) -> NeonResult<Timestamp>
+where
+    C: Context<'a>,
+{
+    if seconds < 0.0 {
+        return cx.throw_range_error(err_msg("second", "greater than zero", seconds));
+    }
+
+    let beats_f64 = seconds / 60.0 * (bpm_cents as f64 / 100.0);
+    let beat_units_f64 = (beats_f64 * beat_units_per_beat()).round();
+    if (u32::MAX as f64) < beat_units_f64 {
+        return cx.throw_range_error(err_msg(
+            "second",
+            "representable by less than 2^32 beat units",
+            seconds,
+        ));
+    }
+
+    Ok(Timestamp::from_beat_units(beat_units_f64 as u32))
+}
+
+/// Convert the timestamp to seconds at the given tempo.
+pub fn to_seconds(timestamp: Timestamp, bpm_cents: u16) -> f64 {
+    let beats_f64 = timestamp.beat_units() as f64 / beat_units_per_beat();
+    beats_f64 / (bpm_cents as f64 / 100.0) * 60.0
+}
+
+/// Read a number of samples from the argument at index `i`, truncated to an integer.
+pub fn samples_argument(cx: &mut FunctionContext<'_>, i: usize) -> NeonResult<usize> {
+    let samples_js: Handle<JsNumber> = cx.argument(i)?;
+    let samples_f64 = samples_js.value(cx);
+    if samples_f64 < 0.0 {
+        return cx.throw_range_error(err_msg("sample", "greater than zero", samples_f64));
+    }
+    if (usize::MAX as f64) < samples_f64 {
+        return cx.throw_range_error(err_msg(
+            "sample",
+            &format!("smaller than 2^{}", usize::BITS),
+            samples_f64,
+        ));
+    }
+    Ok(samples_f64 as usize)
+}
+
+/// Read a BPM from the argument at index `i`, converted to hundredths of a beat per minute.
+///
+/// A BPM that rounds down to zero is only accepted if `allow_zero` is set,
+/// which is the case for the conversions to and from samples that have always accepted it.
+fn bpm_cents_argument(cx: &mut FunctionContext<'_>, i: usize, allow_zero: bool) -> NeonResult<u16> {
+    let bpm_js: Handle<JsNumber> = cx.argument(i)?;
+    let bpm_f64 = bpm_js.value(cx);
+    let bpm_cents_f64 = bpm_f64 * 100.0;
+    if allow_zero && bpm_cents_f64 < 0.0 {
+        return cx.throw_range_error(err_msg("BPM", "greater than zero", bpm_f64));
+    }
+    if !allow_zero && bpm_cents_f64 < 1.0 {
+        return cx.throw_range_error(err_msg("BPM", "at least 0.01", bpm_f64));
+    }
+    if (u16::MAX as f64) < bpm_cents_f64 {
+        return cx.throw_range_error(err_msg("BPM", "smaller than 2^16 / 100", bpm_f64));
+    }
+    Ok(bpm_cents_f64 as u16)
+}
+
+/// Grid lines spaced `step` beat units apart, where every second line is delayed by `delay` to create swing.
+///
+/// `step` is never zero, and `delay` is always smaller than `step`, so the lines stay in order.
+/// Beat units are kept as `u64`, so lines past the end of the timeline can be represented.
+struct Grid {
+    step: u64,
+    delay: u64,
+}
+impl Grid {
+    fn line(&self, i: u64) -> u64 {
+        let delay = if i % 2 == 1 { self.delay } else { 0 };
+        i * self.step + delay
+    }
+
+    /// The index of the last line at or before `t`.
+    fn floor_index(&self, t: u64) -> u64 {
+        let i = t / self.step;
+        if t < self.line(i) {
+            i - 1
+        } else {
+            i
+        }
+    }
+}
+
+/// The most lines that `Timestamp.grid()` returns, so a tiny step over a long range can't exhaust memory.
+///
+/// Asking for more lines throws, rather than silently returning only the first ones.
+const MAX_GRID_LINES: u64 = 1 << 16;
+
+/// Read a grid from the arguments `step: Timestamp` at index `step_i` and `swing?: number` at index `swing_i`.
+fn grid_arguments(cx: &mut FunctionContext<'_>, step_i: usize, swing_i: usize) -> NeonResult<Grid> {
+    let step_js = cx.argument::<JsObject>(step_i)?;
+    let step = timestamp(cx, step_js)?;
+    if step.beat_units() == 0 {
+        return cx.throw_range_error(format!(
+            "Grid must have a step greater than zero. Got {step:?}"
+        ));
+    }
+
+    let swing = match cx.argument_opt(swing_i) {
+        Some(val) if !val.is_a::<JsUndefined, _>(cx) => {
+            let swing_js = val.downcast_or_throw::<JsNumber, _>(cx)?;
+            swing_js.value(cx)
+        }
+        _ => 0.0,
+    };
+    if !(0.0..1.0).contains(&swing) {
+        return cx.throw_range_error(format!(
+            "Grid must have swing value greater than or equal to zero and smaller than one. Got {swing}"
+        ));
+    }
+
+    let step = step.beat_units() as u64;
+    let delay = (swing * step as f64).round() as u64;
+    Ok(Grid {
+        step,
+        delay: delay.min(step - 1),
+    })
+}
+
+/// How to snap a timestamp to a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapMode {
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+pub mod snap_mode {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [("Nearest", "nearest"), ("Floor", "floor"), ("Ceil", "ceil")];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+
+    pub fn get<'a, C>(cx: &mut C, str: Handle<'a, JsString>) -> NeonResult<SnapMode>
+    where
+        C: Context<'a>,
+    {
+        let snap_mode = match str.value(cx).as_str() {
+            "nearest" => SnapMode::Nearest,
+            "floor" => SnapMode::Floor,
+            "ceil" => SnapMode::Ceil,
+            _ => return cx.throw_error(format!("Invalid snap mode: {str:?}")),
+        };
+
+        Ok(snap_mode)
+    }
+}
+
 const STATIC_METHODS: &[(&str, Method)] = &[
     ("min", |mut cx| {
         let a_js = cx.argument::<JsObject>(0)?;
@@ -69,6 +277,38 @@ const STATIC_METHODS: &[(&str, Method)] = &[
 
         Ok(cx.boolean(a == b).as_value(&mut cx))
     }),
+    ("cmp", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = timestamp(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = timestamp(&mut cx, b_js)?;
+
+        let r = match a.beat_units().cmp(&b.beat_units()) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        };
+        Ok(cx.number(r).as_value(&mut cx))
+    }),
+    ("lt", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = timestamp(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = timestamp(&mut cx, b_js)?;
+
+        Ok(cx.boolean(a < b).as_value(&mut cx))
+    }),
+    ("gt", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = timestamp(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = timestamp(&mut cx, b_js)?;
+
+        Ok(cx.boolean(b < a).as_value(&mut cx))
+    }),
     ("add", |mut cx| {
         let a_js = cx.argument::<JsObject>(0)?;
         let a = timestamp(&mut cx, a_js)?;
@@ -123,6 +363,105 @@ const STATIC_METHODS: &[(&str, Method)] = &[
 
         construct(&mut cx, ts * s)
     }),
+    ("div", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = timestamp(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = timestamp(&mut cx, b_js)?;
+
+        if b.beat_units() == 0 {
+            return cx.throw_range_error(format!("Timestamp division by zero: {a:?} / {b:?}"));
+        }
+
+        let r = a.beat_units() / b.beat_units();
+        Ok(cx.number(r as f64).as_value(&mut cx))
+    }),
+    ("mod", |mut cx| {
+        let a_js = cx.argument::<JsObject>(0)?;
+        let a = timestamp(&mut cx, a_js)?;
+
+        let b_js = cx.argument::<JsObject>(1)?;
+        let b = timestamp(&mut cx, b_js)?;
+
+        if b.beat_units() == 0 {
+            return cx.throw_range_error(format!("Timestamp division by zero: {a:?} % {b:?}"));
+        }
+
+        let r = a.beat_units() % b.beat_units();
+        construct(&mut cx, Timestamp::from_beat_units(r))
+    }),
+    ("snap", |mut cx| {
+        let ts_js = cx.argument::<JsObject>(0)?;
+        let ts = timestamp(&mut cx, ts_js)?;
+
+        let grid = grid_arguments(&mut cx, 1, 3)?;
+
+        let mode = match cx.argument_opt(2) {
+            Some(val) if !val.is_a::<JsUndefined, _>(&mut cx) => {
+                let mode_js = val.downcast_or_throw::<JsString, _>(&mut cx)?;
+                snap_mode::get(&mut cx, mode_js)?
+            }
+            _ => SnapMode::Nearest,
+        };
+
+        let t = ts.beat_units() as u64;
+        let floor = grid.line(grid.floor_index(t));
+        let ceil = if floor == t {
+            floor
+        } else {
+            grid.line(grid.floor_index(t) + 1)
+        };
+
+        let r = match mode {
+            SnapMode::Floor => floor,
+            SnapMode::Ceil => ceil,
+            // Ties are snapped to the later line.
+            SnapMode::Nearest if ceil - t <= t - floor && ceil <= u32::MAX as u64 => ceil,
+            SnapMode::Nearest => floor,
+        };
+        if (u32::MAX as u64) < r {
+            return cx.throw_range_error(format!(
+                "Timestamp cannot be snapped past the end of the timeline: {ts:?}"
+            ));
+        }
+
+        construct(&mut cx, Timestamp::from_beat_units(r as u32))
+    }),
+    ("grid", |mut cx| {
+        let start_js = cx.argument::<JsObject>(0)?;
+        let start = timestamp(&mut cx, start_js)?;
+
+        let end_js = cx.argument::<JsObject>(1)?;
+        let end = timestamp(&mut cx, end_js)?;
+
+        let grid = grid_arguments(&mut cx, 2, 3)?;
+
+        let lines = JsArray::new(&mut cx, 0);
+        let start = start.beat_units() as u64;
+        let end = end.beat_units() as u64;
+
+        let mut first = grid.floor_index(start);
+        if grid.line(first) < start {
+            first += 1;
+        }
+        let count = match end.checked_sub(1) {
+            Some(last) => (grid.floor_index(last) + 1).saturating_sub(first),
+            None => 0,
+        };
+        if MAX_GRID_LINES < count {
+            return cx.throw_range_error(format!(
+                "Grid cannot have more than {MAX_GRID_LINES} lines. Got {count}"
+            ));
+        }
+
+        for (len, i) in (first..first + count).enumerate() {
+            let line_js = construct(&mut cx, Timestamp::from_beat_units(grid.line(i) as u32))?;
+            lines.set(&mut cx, len as u32, line_js)?;
+        }
+
+        Ok(lines.as_value(&mut cx))
+    }),
     ("zero", |mut cx| construct(&mut cx, Timestamp::zero())),
     ("infinity", |mut cx| {
         construct(&mut cx, Timestamp::infinity())
@@ -131,15 +470,17 @@ const STATIC_METHODS: &[(&str, Method)] = &[
         let beat_units_js: Handle<JsNumber> = cx.argument(0)?;
         let beat_units_f64 = beat_units_js.value(&mut cx);
 
-        if beat_units_f64 < 0.0 {
-            return cx.throw_range_error(err_msg("beat unit", "greater than zero", beat_units_f64));
-        }
-        if (u32::MAX as f64) < beat_units_f64 {
-            return cx.throw_range_error(err_msg("beat unit", "smaller than 2^32", beat_units_f64));
-        }
+        let timestamp = from_beat_units(&mut cx, beat_units_f64, false)?;
+        construct(&mut cx, timestamp)
+    }),
+    ("fromJSON", |mut cx| {
+        let json_js: Handle<JsObject> = cx.argument(0)?;
+        let beat_units_js: Handle<JsNumber> = json_js.get(&mut cx, "beatUnits")?;
+        let beat_units_f64 = beat_units_js.value(&mut cx);
 
-        let beat_units = beat_units_f64 as u32;
-        construct(&mut cx, Timestamp::from_beat_units(beat_units))
+        // Serialized timestamps are always whole beat units, so anything else is invalid rather than rounded.
+        let timestamp = from_beat_units(&mut cx, beat_units_f64, true)?;
+        construct(&mut cx, timestamp)
     }),
     ("fromBeats", |mut cx| {
         let beats_js: Handle<JsNumber> = cx.argument(0)?;
@@ -148,27 +489,24 @@ const STATIC_METHODS: &[(&str, Method)] = &[
         if beats_f64 < 0.0 {
             return cx.throw_range_error(err_msg("beat", "greater than zero", beats_f64));
         }
-        if (u32::MAX as f64) < beats_f64 {
-            return cx.throw_range_error(err_msg("beat", "smaller than 2^32", beats_f64));
+        let beat_units_f64 = (beats_f64 * beat_units_per_beat()).round();
+        if (u32::MAX as f64) < beat_units_f64 {
+            return cx.throw_range_error(err_msg("beat", "smaller than 2^22", beats_f64));
         }
 
-        let beats = beats_f64 as u32;
-        construct(&mut cx, Timestamp::from_beats(beats))
+        let beat_units = beat_units_f64 as u32;
+        construct(&mut cx, Timestamp::from_beat_units(beat_units))
+    }),
+    ("fromSeconds", |mut cx| {
+        let seconds_js: Handle<JsNumber> = cx.argument(0)?;
+        let seconds_f64 = seconds_js.value(&mut cx);
+
+        let bpm_cents = bpm_cents_argument(&mut cx, 1, false)?;
+        let timestamp = from_seconds(&mut cx, seconds_f64, bpm_cents)?;
+        construct(&mut cx, timestamp)
     }),
     ("fromSamples", |mut cx| {
-        let samples_js: Handle<JsNumber> = cx.argument(0)?;
-        let samples_f64 = samples_js.value(&mut cx);
-        if samples_f64 < 0.0 {
-            return cx.throw_range_error(err_msg("sample", "greater than zero", samples_f64));
-        }
-        if (usize::MAX as f64) < samples_f64 {
-            return cx.throw_range_error(err_msg(
-                "sample",
-                &format!("smaller than 2^{}", usize::BITS),
-                samples_f64,
-            ));
-        }
-        let samples = samples_f64 as usize;
+        let samples = samples_argument(&mut cx, 0)?;
 
         let sample_rate_js: Handle<JsNumber> = cx.argument(1)?;
         let sample_rate_f64 = sample_rate_js.value(&mut cx);
@@ -188,16 +526,7 @@ const STATIC_METHODS: &[(&str, Method)] = &[
         }
         let sample_rate = sample_rate_f64 as u32;
 
-        let bpm_js: Handle<JsNumber> = cx.argument(2)?;
-        let bpm_f64 = bpm_js.value(&mut cx);
-        let bpm_cents_f64 = bpm_f64 * 100.0;
-        if bpm_cents_f64 < 0.0 {
-            return cx.throw_range_error(err_msg("BPM", "greater than zero", bpm_f64));
-        }
-        if (u16::MAX as f64) < bpm_cents_f64 {
-            return cx.throw_range_error(err_msg("BPM", "smaller than 2^16 / 100", bpm_f64));
-        }
-        let bpm_cents = bpm_cents_f64 as u16;
+        let bpm_cents = bpm_cents_argument(&mut cx, 2, true)?;
 
         construct(
             &mut cx,
@@ -207,6 +536,35 @@ const STATIC_METHODS: &[(&str, Method)] = &[
 ];
 
 const METHODS: &[(&str, Method)] = &[
+    ("toSeconds", |mut cx| {
+        let this = cx.this()?;
+
+        let bpm_cents = bpm_cents_argument(&mut cx, 0, false)?;
+        let timestamp = timestamp(&mut cx, this)?;
+        let seconds = to_seconds(timestamp, bpm_cents);
+        Ok(cx.number(seconds).as_value(&mut cx))
+    }),
+    ("toJSON", |mut cx| {
+        let this = cx.this()?;
+        let timestamp = timestamp(&mut cx, this)?;
+
+        let json_js = cx.empty_object();
+        let beat_units_js = cx.number(timestamp.beat_units() as f64);
+        json_js.set(&mut cx, "beatUnits", beat_units_js)?;
+        Ok(json_js.as_value(&mut cx))
+    }),
+    ("toString", |mut cx| {
+        let this = cx.this()?;
+        let timestamp = timestamp(&mut cx, this)?;
+        let str = format!("Timestamp({} beat units)", timestamp.beat_units());
+        Ok(cx.string(str).as_value(&mut cx))
+    }),
+    ("valueOf", |mut cx| {
+        let this = cx.this()?;
+        let timestamp = timestamp(&mut cx, this)?;
+        let beat_units = timestamp.beat_units();
+        Ok(cx.number(beat_units as f64).as_value(&mut cx))
+    }),
     ("getBeatUnits", |mut cx| {
         let this = cx.this()?;
         let timestamp = timestamp(&mut cx, this)?;
@@ -240,16 +598,7 @@ const METHODS: &[(&str, Method)] = &[
         }
         let sample_rate = sample_rate_f64 as u32;
 
-        let bpm_js: Handle<JsNumber> = cx.argument(1)?;
-        let bpm_f64 = bpm_js.value(&mut cx);
-        let bpm_cents_f64 = bpm_f64 * 100.0;
-        if bpm_cents_f64 < 0.0 {
-            return cx.throw_range_error(err_msg("BPM", "greater than zero", bpm_f64));
-        }
-        if (u16::MAX as f64) < bpm_cents_f64 {
-            return cx.throw_range_error(err_msg("BPM", "smaller than 2^16 / 100", bpm_f64));
-        }
-        let bpm_cents = bpm_cents_f64 as u16;
+        let bpm_cents = bpm_cents_argument(&mut cx, 1, true)?;
 
         let timestamp = timestamp(&mut cx, this)?;
         let beat_units = timestamp.samples(sample_rate, bpm_cents);
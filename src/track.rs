@@ -1,8 +1,10 @@
 use neon::prelude::*;
 use std::ops::Deref;
 
-use crate::clip::audio_clip::AudioClipKeyWrapper;
+use crate::clip::{audio_clip::AudioClipKeyWrapper, fade};
+use crate::overlap::{self, OverlapMode};
 use crate::stored_clip::stored_audio_clip::StoredAudioClipKeyWrapper;
+use crate::timeline_edit;
 use crate::utils::ResultExt;
 use crate::{clip::audio_clip, encapsulator::unpack, timestamp::timestamp};
 use crate::{
@@ -79,6 +81,23 @@ pub mod audio_track {
         )
     }
 
+    pub fn unpack_audio_track_keys<'a, C>(
+        cx: &mut C,
+        array: Handle<'a, JsArray>,
+    ) -> NeonResult<Vec<adae::AudioTrackKey>>
+    where
+        C: Context<'a>,
+    {
+        let tracks_js = array.to_vec(cx)?;
+        tracks_js
+            .into_iter()
+            .map(|value| {
+                let track_js: Handle<JsObject> = value.downcast_or_throw(cx)?;
+                unpack_audio_track_key(cx, track_js)
+            })
+            .collect()
+    }
+
     fn unpack_this_audio_track_key(
         cx: &mut FunctionContext<'_>,
     ) -> NeonResult<adae::AudioTrackKey> {
@@ -133,6 +152,154 @@ pub mod audio_track {
         )
     }
 
+    /// Copy the mixer settings, input channels, monitor mode and every clip of the audio track `from` onto the empty audio track `to`.
+    fn copy_audio_track<'a, C>(
+        cx: &mut C,
+        engine: &mut adae::Engine,
+        from: adae::AudioTrackKey,
+        to: adae::AudioTrackKey,
+    ) -> NeonResult<()>
+    where
+        C: Context<'a>,
+    {
+        let mixer_track_key = engine.audio_mixer_track_key(from).or_throw(cx)?;
+        let mixer_track = engine.mixer_track_mut(mixer_track_key).or_throw(cx)?;
+        let volume = mixer_track.volume();
+        let panning = mixer_track.panning();
+
+        let new_mixer_track_key = engine.audio_mixer_track_key(to).or_throw(cx)?;
+        let new_mixer_track = engine.mixer_track_mut(new_mixer_track_key).or_throw(cx)?;
+        new_mixer_track.set_volume(volume);
+        new_mixer_track.set_panning(panning);
+
+        // The copy is left disarmed, so it doesn't record the same input twice.
+        let input_channels = engine
+            .audio_track_input_channels(from)
+            .or_throw(cx)?
+            .to_vec();
+        engine
+            .audio_track_set_input_channels(to, input_channels)
+            .or_throw(cx)?;
+        let monitor_mode = engine.audio_track_monitor_mode(from).or_throw(cx)?;
+        engine
+            .audio_track_set_monitor_mode(to, monitor_mode)
+            .or_throw(cx)?;
+
+        let timeline_track_key = engine.audio_timeline_track_key(from).or_throw(cx)?;
+        let new_timeline_track_key = engine.audio_timeline_track_key(to).or_throw(cx)?;
+        let clips: Vec<_> = engine
+            .audio_clips(timeline_track_key)
+            .or_throw(cx)?
+            .map(|clip| (clip.key, clip.start()))
+            .collect();
+        for (clip_key, start) in clips {
+            engine
+                .audio_clip_duplicate(clip_key, start, new_timeline_track_key)
+                .or_throw(cx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the stored clip given as the first argument at the start given as the second argument,
+    /// with the optional length given as the third argument, making room for it according to `overlap_mode`.
+    ///
+    /// Returns the new clip, along with the states of the clips that were removed to make room for it.
+    /// If the clip can't be added, the clips in the way are left as they were.
+    fn add_clip<'a>(
+        cx: &mut FunctionContext<'a>,
+        overlap_mode: OverlapMode,
+    ) -> NeonResult<(Handle<'a, JsObject>, Vec<adae::AudioClipState>)> {
+        assert_this_not_deleted(cx)?;
+
+        let audio_clip_js = cx.argument::<JsObject>(0)?;
+        let audio_clip_key = unpack(
+            cx,
+            audio_clip_js,
+            |_, (_, stored_clip_key): &(SharedEngine, StoredAudioClipKeyWrapper)| {
+                Ok(**stored_clip_key)
+            },
+        )?;
+
+        let start_js = cx.argument::<JsObject>(1)?;
+        let start = timestamp(cx, start_js)?;
+
+        let length_js_val = cx.argument_opt(2);
+        let length_js = match length_js_val {
+            Some(val) => {
+                let is_null = val.is_a::<JsNull, _>(cx);
+                let is_undefined = val.is_a::<JsUndefined, _>(cx);
+                if is_null || is_undefined {
+                    None
+                } else {
+                    Some(val.downcast_or_throw(cx)?)
+                }
+            }
+            None => None,
+        };
+        let length = match length_js {
+            Some(length_js) => Some(timestamp(cx, length_js)?),
+            None => None,
+        };
+
+        let (shared_engine, audio_track_key) = unpack_this(
+            cx,
+            |_, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                Ok((shared_engine.clone(), **audio_track_key))
+            },
+        )?;
+
+        let (key, removed) = shared_engine.with_inner(cx, |cx, engine| {
+            let full_length = match length {
+                Some(length) => length,
+                None => {
+                    let bpm_cents = engine.bpm_cents();
+                    let stored_clip = engine.stored_audio_clip(audio_clip_key).or_throw(cx)?;
+                    adae::Timestamp::from_samples(
+                        stored_clip.length(),
+                        stored_clip.sample_rate(),
+                        bpm_cents,
+                    )
+                }
+            };
+
+            let ((key, removed), _) = timeline_edit::atomic(cx, engine, |cx, engine, edit| {
+                let room = overlap::make_room(
+                    cx,
+                    &shared_engine,
+                    engine,
+                    edit,
+                    audio_track_key,
+                    start,
+                    full_length,
+                    None,
+                    overlap_mode,
+                )?;
+                let placed_length = if room.length == full_length {
+                    length
+                } else {
+                    Some(room.length)
+                };
+
+                let timeline_track_key = engine
+                    .audio_timeline_track_key(audio_track_key)
+                    .or_throw(cx)?;
+                let key = engine
+                    .add_audio_clip(timeline_track_key, audio_clip_key, start, placed_length)
+                    .or_throw(cx)?;
+                edit.record_created(key);
+
+                let removed = room.finish(cx, engine, key)?;
+                Ok((key, removed))
+            })?;
+
+            Ok((key, removed))
+        })?;
+
+        let clip_js = audio_clip::construct(cx, key, shared_engine)?;
+        Ok((clip_js, removed))
+    }
+
     const METHODS: &[(&str, Method)] = &[
         ("getKey", |mut cx| {
             assert_this_not_deleted(&mut cx)?;
@@ -189,53 +356,20 @@ pub mod audio_track {
             )
         }),
         ("addClip", |mut cx| {
-            assert_this_not_deleted(&mut cx)?;
-
-            let audio_clip_js = cx.argument::<JsObject>(0)?;
-            let audio_clip_key = unpack(
-                &mut cx,
-                audio_clip_js,
-                |_, (_, stored_clip_key): &(SharedEngine, StoredAudioClipKeyWrapper)| {
-                    Ok(**stored_clip_key)
-                },
-            )?;
-
-            let start_js = cx.argument::<JsObject>(1)?;
-            let start = timestamp(&mut cx, start_js)?;
-
-            let length_js_val = cx.argument_opt(2);
-            let length_js = match length_js_val {
-                Some(val) => {
-                    let is_null = val.is_a::<JsNull, _>(&mut cx);
-                    let is_undefined = val.is_a::<JsUndefined, _>(&mut cx);
-                    if is_null || is_undefined {
-                        None
-                    } else {
-                        Some(val.downcast_or_throw(&mut cx)?)
-                    }
-                }
-                None => None,
-            };
-            let length = match length_js {
-                Some(length_js) => Some(timestamp(&mut cx, length_js)?),
-                None => None,
-            };
-
-            unpack_this(
-                &mut cx,
-                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
-                    shared_engine.with_inner(cx, |cx, engine| {
-                        let timeline_track_key = engine
-                            .audio_timeline_track_key(**audio_track_key)
-                            .or_throw(cx)?;
-                        let key = engine
-                            .add_audio_clip(timeline_track_key, audio_clip_key, start, length)
-                            .or_throw(cx)?;
-
-                        Ok(audio_clip::construct(cx, key, shared_engine.clone())?.as_value(cx))
-                    })
-                },
-            )
+            let overlap_mode = overlap::argument_opt(&mut cx, 3)?.unwrap_or(OverlapMode::Reject);
+            let (clip_js, _) = add_clip(&mut cx, overlap_mode)?;
+            Ok(clip_js.as_value(&mut cx))
+        }),
+        // Same as `addClip`, but also returns the states of the clips that were removed to make room.
+        ("placeClip", |mut cx| {
+            let overlap_mode = overlap::argument_opt(&mut cx, 3)?.unwrap_or(OverlapMode::Reject);
+            let (clip_js, removed) = add_clip(&mut cx, overlap_mode)?;
+            let removed_js = audio_clip::encapsulate_states(&mut cx, removed)?;
+
+            let result_js = cx.empty_object();
+            result_js.set(&mut cx, "clip", clip_js)?;
+            result_js.set(&mut cx, "removed", removed_js)?;
+            Ok(result_js.as_value(&mut cx))
         }),
         ("deleteClip", |mut cx| {
             assert_this_not_deleted(&mut cx)?;
@@ -352,6 +486,236 @@ pub mod audio_track {
                 },
             )
         }),
+        ("crossfade", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+
+            let left_js = cx.argument::<JsObject>(0)?;
+            let left_key = unpack(
+                &mut cx,
+                left_js,
+                |_, (_, clip_key): &(SharedEngine, AudioClipKeyWrapper)| Ok(**clip_key),
+            )?;
+            let right_js = cx.argument::<JsObject>(1)?;
+            let right_key = unpack(
+                &mut cx,
+                right_js,
+                |_, (_, clip_key): &(SharedEngine, AudioClipKeyWrapper)| Ok(**clip_key),
+            )?;
+            let fade = fade::argument(&mut cx, 2, adae::FadeCurve::EqualPower)?;
+
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        for clip_key in [left_key, right_key] {
+                            if engine.audio_clip_track_key(clip_key).or_throw(cx)?
+                                != **audio_track_key
+                            {
+                                return cx.throw_error("Clips must both be on this track.");
+                            }
+                        }
+
+                        engine
+                            .audio_clip_crossfade(left_key, right_key, fade)
+                            .or_else(|e| {
+                                cx.throw_error(format!("Failed to crossfade clips: {e}"))
+                            })?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("duplicate", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let new_audio_track_key = engine.add_audio_track().or_throw(cx)?;
+
+                        let copied =
+                            copy_audio_track(cx, engine, **audio_track_key, new_audio_track_key);
+                        if copied.is_err() {
+                            // Don't leave a half-built copy behind.
+                            let _ = engine.delete_audio_track(new_audio_track_key);
+                        }
+                        copied?;
+
+                        let new_audio_track_js =
+                            construct(cx, new_audio_track_key, shared_engine.clone())?;
+                        Ok(new_audio_track_js.as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getTakeRegions", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let timeline_track_key = engine
+                            .audio_timeline_track_key(**audio_track_key)
+                            .or_throw(cx)?;
+                        let clip_keys: Vec<_> = engine
+                            .audio_clips(timeline_track_key)
+                            .or_throw(cx)?
+                            .map(|clip| clip.key)
+                            .collect();
+
+                        // Only clips with more than one take are regions that can be comped.
+                        let regions_js = cx.empty_array();
+                        let mut i: u32 = 0;
+                        for clip_key in clip_keys {
+                            let takes = engine.audio_clip_takes(clip_key).or_throw(cx)?.to_vec();
+                            if takes.len() < 2 {
+                                continue;
+                            }
+
+                            let active_take =
+                                engine.audio_clip_active_take(clip_key).or_throw(cx)?;
+                            let region_js = cx.empty_object();
+                            let clip_js =
+                                audio_clip::construct(cx, clip_key, shared_engine.clone())?;
+                            region_js.set(cx, "clip", clip_js)?;
+                            let takes_js = audio_clip::construct_takes(cx, takes, shared_engine)?;
+                            region_js.set(cx, "takes", takes_js)?;
+                            let active_take_js = cx.number(active_take as f64);
+                            region_js.set(cx, "activeTake", active_take_js)?;
+
+                            regions_js.set(cx, i, region_js)?;
+                            i += 1;
+                        }
+
+                        Ok(regions_js.as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getMonitorMode", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let monitor_mode = engine
+                            .audio_track_monitor_mode(**audio_track_key)
+                            .or_throw(cx)?;
+                        monitor_mode::construct(cx, &monitor_mode)
+                    })
+                },
+            )
+        }),
+        ("setMonitorMode", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            let monitor_mode_js = cx.argument::<JsString>(0)?;
+            let monitor_mode = monitor_mode::get(&mut cx, monitor_mode_js)?;
+
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        engine
+                            .audio_track_set_monitor_mode(**audio_track_key, monitor_mode)
+                            .or_throw(cx)?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("isRecordArmed", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let armed = engine
+                            .audio_track_record_armed(**audio_track_key)
+                            .or_throw(cx)?;
+                        Ok(cx.boolean(armed).as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("setRecordArmed", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            let armed = cx.argument::<JsBoolean>(0)?.value(&mut cx);
+
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        engine
+                            .audio_track_set_record_armed(**audio_track_key, armed)
+                            .or_throw(cx)?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("getInputChannels", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        let channels = engine
+                            .audio_track_input_channels(**audio_track_key)
+                            .or_throw(cx)?
+                            .to_vec();
+
+                        let channels_js = JsArray::new(cx, channels.len());
+                        for (i, channel) in channels.into_iter().enumerate() {
+                            let channel_js = cx.number(channel);
+                            channels_js.set(cx, i as u32, channel_js)?;
+                        }
+                        Ok(channels_js.as_value(cx))
+                    })
+                },
+            )
+        }),
+        ("setInputChannels", |mut cx| {
+            assert_this_not_deleted(&mut cx)?;
+
+            let channels_js = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+            if channels_js.is_empty() {
+                return cx.throw_range_error("At least one input channel must be given");
+            }
+            let mut channels = Vec::with_capacity(channels_js.len());
+            for channel_js in channels_js {
+                let channel = channel_js
+                    .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                    .value(&mut cx);
+                if channel.fract() != 0.0 || channel < 0.0 || (u16::MAX as f64) < channel {
+                    return cx.throw_range_error(format!(
+                        "Input channel must be an integer representable as an unsigned 16-bit integer. Got {channel}"
+                    ));
+                }
+                channels.push(channel as u16);
+            }
+
+            unpack_this(
+                &mut cx,
+                |cx, (shared_engine, audio_track_key): &(SharedEngine, AudioTrackKeyWrapper)| {
+                    shared_engine.with_inner(cx, |cx, engine| {
+                        // Channels can only be checked against an input device if one is configured.
+                        if let Some(input) = &engine.config().input {
+                            let available = input.config.channels;
+                            if let Some(channel) = channels.iter().find(|&&c| available <= c) {
+                                return cx.throw_range_error(format!(
+                                    "Input device only has {available} channels, so channel {channel} does not exist"
+                                ));
+                            }
+                        }
+
+                        engine
+                            .audio_track_set_input_channels(**audio_track_key, channels)
+                            .or_throw(cx)?;
+                        Ok(cx.undefined().as_value(cx))
+                    })
+                },
+            )
+        }),
         ("delete", |mut cx| {
             assert_this_not_deleted(&mut cx)?;
             unpack_this(
@@ -387,6 +751,53 @@ pub mod audio_track {
     impl Finalize for AudioTrackStateWrapper {}
 }
 
+pub mod monitor_mode {
+    use super::*;
+
+    pub fn object<'a, C>(cx: &mut C) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+    {
+        let obj = cx.empty_object();
+
+        let fields = [("Off", "off"), ("Auto", "auto"), ("Always", "always")];
+
+        for (name, val) in fields.iter() {
+            let str = cx.string(*val);
+            obj.set(cx, *name, str)?;
+        }
+
+        Ok(obj)
+    }
+
+    pub fn construct<'a, C>(cx: &mut C, monitor_mode: &adae::MonitorMode) -> JsResult<'a, JsValue>
+    where
+        C: Context<'a>,
+    {
+        let monitor_mode_str = match monitor_mode {
+            adae::MonitorMode::Off => "off",
+            adae::MonitorMode::Auto => "auto",
+            adae::MonitorMode::Always => "always",
+        };
+
+        Ok(cx.string(monitor_mode_str).as_value(cx))
+    }
+
+    pub fn get<'a, C>(cx: &mut C, str: Handle<'a, JsString>) -> NeonResult<adae::MonitorMode>
+    where
+        C: Context<'a>,
+    {
+        let monitor_mode = match str.value(cx).as_str() {
+            "off" => adae::MonitorMode::Off,
+            "auto" => adae::MonitorMode::Auto,
+            "always" => adae::MonitorMode::Always,
+            _ => return cx.throw_error(format!("Invalid monitor mode: {str:?}")),
+        };
+
+        Ok(monitor_mode)
+    }
+}
+
 // Shared methods
 fn get_panning<'a>(
     cx: &mut FunctionContext<'a>,
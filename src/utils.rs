@@ -1,6 +1,11 @@
 use std::error::Error;
 
-use neon::{context::Context, result::NeonResult};
+use neon::{
+    context::Context,
+    handle::Handle,
+    result::NeonResult,
+    types::{JsNull, JsUndefined, JsValue},
+};
 
 /// A trait for extending the `Result` type with methods which are convenient within this specific project.
 pub trait ResultExt<T> {
@@ -21,3 +26,11 @@ where
         self.or_else(|e| cx.throw_error(format!("{e}")))
     }
 }
+
+/// Whether the value is `undefined` or `null`, which optional arguments and properties treat alike.
+pub fn is_nullish<'a, C>(cx: &mut C, val: Handle<JsValue>) -> bool
+where
+    C: Context<'a>,
+{
+    val.is_a::<JsUndefined, _>(cx) || val.is_a::<JsNull, _>(cx)
+}
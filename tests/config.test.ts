@@ -0,0 +1,359 @@
+import { Engine, FailoverTarget, config } from "../index";
+
+describe("Config", () => {
+    let engine: Engine;
+    beforeEach(() => {
+        engine = Engine.getDummy();
+    });
+    afterEach(() => {
+        engine.close();
+    });
+
+    test("toJSON()", () => {
+        const engineConfig = engine.getConfig();
+        const json = engineConfig.toJSON();
+
+        expect(json.host).toStrictEqual(
+            engineConfig.getOutputDevice().host().name(),
+        );
+        expect(json.outputDevice).toStrictEqual(
+            engineConfig.getOutputDevice().name(),
+        );
+        expect(json.outputConfig).toStrictEqual(
+            engineConfig.getOutputConfig(),
+        );
+    });
+
+    test("withThreadConfig()", () => {
+        const threadConfig = {
+            realtimePriority: true,
+            cpuAffinity: [0, 1],
+            workerThreads: 2,
+        };
+        const engineConfig = engine.getConfig();
+        const newConfig = engineConfig.withThreadConfig(threadConfig);
+
+        expect(newConfig.getThreadConfig()).toStrictEqual(threadConfig);
+        expect(newConfig.toJSON().threadConfig).toStrictEqual(threadConfig);
+        expect(newConfig.getOutputConfig()).toStrictEqual(
+            engineConfig.getOutputConfig(),
+        );
+        // The original is left unchanged
+        expect(engineConfig.getThreadConfig()).not.toStrictEqual(threadConfig);
+    });
+
+    test("constructor takes thread config", () => {
+        const threadConfig = {
+            realtimePriority: true,
+            cpuAffinity: [0],
+            workerThreads: 2,
+        };
+        const engineConfig = engine.getConfig();
+        const outputDevice = engineConfig.getOutputDevice();
+        const outputConfig = engineConfig.getOutputConfig();
+
+        const withThreadConfig = new config.Config(
+            outputDevice,
+            outputConfig,
+            null,
+            undefined,
+            threadConfig,
+        );
+        expect(withThreadConfig.getThreadConfig()).toStrictEqual(threadConfig);
+
+        const defaultThreadConfig = config.Config.default().getThreadConfig();
+        expect(
+            new config.Config(outputDevice, outputConfig).getThreadConfig(),
+        ).toStrictEqual(defaultThreadConfig);
+        expect(
+            new config.Config(
+                outputDevice,
+                outputConfig,
+                null,
+                undefined,
+                null,
+            ).getThreadConfig(),
+        ).toStrictEqual(defaultThreadConfig);
+        expect(() =>
+            new config.Config(outputDevice, outputConfig, null, undefined, {
+                ...threadConfig,
+                workerThreads: 0,
+            }),
+        ).toThrow(RangeError);
+    });
+
+    test("withThreadConfig() rejects invalid options", () => {
+        const engineConfig = engine.getConfig();
+        const valid = {
+            realtimePriority: false,
+            cpuAffinity: null,
+            workerThreads: null,
+        };
+        for (const invalid of [
+            { ...valid, cpuAffinity: [] },
+            { ...valid, cpuAffinity: [-1] },
+            { ...valid, cpuAffinity: [65536] },
+            { ...valid, workerThreads: 0 },
+            { ...valid, workerThreads: 1.5 },
+        ]) {
+            expect(() => engineConfig.withThreadConfig(invalid)).toThrow(
+                RangeError,
+            );
+        }
+    });
+
+    test("fromJSON() keeps thread config", () => {
+        const threadConfig = {
+            realtimePriority: true,
+            cpuAffinity: null,
+            workerThreads: 4,
+        };
+        const json = engine.getConfig().withThreadConfig(threadConfig).toJSON();
+        const { config: loaded } = config.Config.fromJSON(json);
+        expect(loaded.getThreadConfig()).toStrictEqual(threadConfig);
+    });
+
+    test("fromJSON() uses default thread config for null", () => {
+        const json = { ...engine.getConfig().toJSON(), threadConfig: null };
+        const { config: loaded } = config.Config.fromJSON(json);
+        expect(loaded.getThreadConfig()).toStrictEqual(
+            config.Config.fromJSON({
+                ...engine.getConfig().toJSON(),
+                threadConfig: undefined,
+            }).config.getThreadConfig(),
+        );
+    });
+
+    test("getRealtimePriority()", () => {
+        expect(Object.values(config.RealtimePriority)).toContain(
+            engine.getRealtimePriority(),
+        );
+    });
+
+    test("Dummy engine has no input", () => {
+        const engineConfig = engine.getConfig();
+        expect(engineConfig.getInputDevice()).toBeNull();
+        expect(engineConfig.getInputConfig()).toBeNull();
+        expect(engineConfig.toJSON().input).toBeNull();
+    });
+
+    test("fromJSON() leaves out missing input device", () => {
+        const json = {
+            ...engine.getConfig().toJSON(),
+            input: {
+                host: "This host does not exist",
+                device: "This device does not exist",
+                config: {
+                    channels: 2,
+                    sampleFormat: config.SampleFormat.Float32,
+                    bufferSize: null,
+                },
+            },
+        };
+        const { config: loaded, fallback } = config.Config.fromJSON(json);
+        expect(loaded.getInputDevice()).toBeNull();
+        expect(fallback).toContain(config.Fallback.NoInputDevice);
+    });
+
+    test("Host.inputDevices()", () => {
+        const inputDevices = config.Host.default().inputDevices();
+        expect(Array.isArray(inputDevices)).toStrictEqual(true);
+        for (const device of inputDevices) {
+            expect(typeof device.name()).toStrictEqual("string");
+            expect(device.host().name()).toStrictEqual(
+                config.Host.default().name(),
+            );
+        }
+    });
+
+    test("toJSON() is used by JSON.stringify()", () => {
+        const engineConfig = engine.getConfig();
+        const json = JSON.parse(JSON.stringify(engineConfig));
+        expect(json).toStrictEqual(engineConfig.toJSON());
+    });
+
+    test("fromJSON() falls back to default host", () => {
+        const json = {
+            ...engine.getConfig().toJSON(),
+            host: "This host does not exist",
+        };
+
+        // Without a sound card there is no output device to fall back to
+        let defaultOutputDevice: config.OutputDevice | null;
+        try {
+            defaultOutputDevice = config.Host.default().defaultOutputDevice();
+        } catch {
+            defaultOutputDevice = null;
+        }
+        if (defaultOutputDevice === null) {
+            expect(() => config.Config.fromJSON(json)).toThrow();
+            return;
+        }
+
+        const { config: loaded, fallback } = config.Config.fromJSON(json);
+        expect(fallback).toStrictEqual([config.Fallback.DefaultHost]);
+        expect(loaded.getOutputDevice().host().name()).toStrictEqual(
+            config.Host.default().name(),
+        );
+    });
+
+    test("fromJSON() rejects invalid output config", () => {
+        const json = {
+            ...engine.getConfig().toJSON(),
+            outputConfig: {
+                channels: -1,
+                sampleFormat: config.SampleFormat.Float32,
+                sampleRate: 48_000,
+                bufferSize: null,
+            },
+        };
+        expect(() => config.Config.fromJSON(json)).toThrow();
+    });
+});
+
+describe("OutputDevice", () => {
+    let engine: Engine;
+    beforeEach(() => {
+        engine = Engine.getDummy();
+    });
+    afterEach(() => {
+        engine.close();
+    });
+
+    test("validateConfig() accepts nearestSupportedConfig()", () => {
+        const device = engine.getConfig().getOutputDevice();
+        const desired = engine.getConfig().getOutputConfig();
+        const nearest = device.nearestSupportedConfig(desired);
+        expect(device.validateConfig(nearest)).toStrictEqual([]);
+    });
+
+    test("validateConfig() reports unsupported fields", () => {
+        const device = engine.getConfig().getOutputDevice();
+        const outputConfig = {
+            ...engine.getConfig().getOutputConfig(),
+            sampleRate: 0,
+        };
+        expect(device.validateConfig(outputConfig)).toContain("sampleRate");
+    });
+
+    test("nearestSupportedConfig() clamps sample rate", () => {
+        const device = engine.getConfig().getOutputDevice();
+        const desired = {
+            ...engine.getConfig().getOutputConfig(),
+            sampleRate: 4_000_000_000,
+        };
+        const nearest = device.nearestSupportedConfig(desired);
+        const maxSampleRate = Math.max(
+            ...device.supportedConfigRanges().map(r => r.sampleRate().max),
+        );
+        expect(nearest.sampleRate).toBeLessThanOrEqual(maxSampleRate);
+    });
+});
+
+describe("Device watching", () => {
+    test("watchDevices() returns a watcher that can be stopped", () => {
+        const watcher = config.Host.default().watchDevices(() => {});
+        expect(watcher).toBeInstanceOf(Object);
+        watcher.stop();
+        // Stopping twice is harmless
+        watcher.stop();
+    });
+
+    test("DeviceDirection", () => {
+        expect(Object.values(config.DeviceDirection)).toStrictEqual([
+            "output",
+            "input",
+        ]);
+    });
+
+    test("watchDevices() accepts null options", () => {
+        const host = config.Host.default();
+        host.watchDevices(() => {}, null).stop();
+        host.watchDevices(() => {}, { interval: null }).stop();
+    });
+
+    test("watchDevices() rejects invalid interval", () => {
+        const host = config.Host.default();
+        for (const interval of [0, -1, Infinity, NaN]) {
+            expect(() => host.watchDevices(() => {}, { interval })).toThrow(
+                RangeError,
+            );
+        }
+    });
+
+    test("watchDevices() is quiet when nothing changes", async () => {
+        const callback = jest.fn();
+        const watcher = config.Host.default().watchDevices(callback, {
+            interval: 10,
+        });
+        await new Promise(resolve => setTimeout(resolve, 100));
+        watcher.stop();
+        expect(callback).not.toHaveBeenCalled();
+    });
+
+    test("onOutputDeviceLost() on dummy engine", async () => {
+        const engine = Engine.getDummy();
+        const callback = jest.fn();
+        const watcher = engine.onOutputDeviceLost(callback, { interval: 10 });
+        await new Promise(resolve => setTimeout(resolve, 100));
+        watcher.stop();
+        engine.close();
+        expect(callback).not.toHaveBeenCalled();
+    });
+
+    test("onOutputDeviceLost() rejects invalid interval", () => {
+        const engine = Engine.getDummy();
+        expect(() =>
+            engine.onOutputDeviceLost(() => {}, { interval: -1 }),
+        ).toThrow(RangeError);
+        engine.close();
+    });
+
+    test("enableFailover() keeps engine running", async () => {
+        const engine = Engine.getDummy();
+        const callback = jest.fn();
+        const watcher = engine.enableFailover(
+            FailoverTarget.DefaultOutputDevice,
+            callback,
+            { interval: 10 },
+        );
+        await new Promise(resolve => setTimeout(resolve, 100));
+        watcher.stop();
+
+        // The dummy output device never disappears
+        expect(callback).not.toHaveBeenCalled();
+        expect(() => engine.getPlayheadPosition()).not.toThrow();
+        engine.close();
+    });
+
+    test("enableFailover() replaces the previous watcher", async () => {
+        const engine = Engine.getDummy();
+        const first = jest.fn();
+        const second = jest.fn();
+        engine.enableFailover(FailoverTarget.Dummy, first, { interval: 10 });
+        const watcher = engine.enableFailover(FailoverTarget.Dummy, second, {
+            interval: 10,
+        });
+        await new Promise(resolve => setTimeout(resolve, 100));
+        watcher.stop();
+
+        expect(first).not.toHaveBeenCalled();
+        expect(second).not.toHaveBeenCalled();
+        engine.close();
+    });
+
+    test("enableFailover() throws after close", () => {
+        const engine = Engine.getDummy();
+        engine.close();
+        expect(() =>
+            engine.enableFailover(FailoverTarget.Dummy, () => {}),
+        ).toThrow();
+    });
+
+    test("enableFailover() rejects invalid target", () => {
+        const engine = Engine.getDummy();
+        const target = "nowhere" as FailoverTarget;
+        expect(() => engine.enableFailover(target, () => {})).toThrow();
+        engine.close();
+    });
+});
@@ -10,6 +10,12 @@ import {
     AudioTrack,
     Track,
     AudioClip,
+    FadeCurve,
+    OverlapMode,
+    OverlapError,
+    Offset,
+    FailoverTarget,
+    MonitorMode,
 } from "../index";
 
 describe("Engine", () => {
@@ -57,6 +63,82 @@ describe("Engine", () => {
             expect(engine.pause()).toBeUndefined();
         });
 
+        test("getLatency()", () => {
+            const latency = engine.getLatency();
+            for (const value of [
+                latency.input,
+                latency.output,
+                latency.processing,
+            ]) {
+                expect(Number.isInteger(value)).toStrictEqual(true);
+                expect(value).toBeGreaterThanOrEqual(0);
+            }
+        });
+
+        test("getStats()", () => {
+            const stats = engine.getStats();
+            expect(stats.averageLoad).toBeGreaterThanOrEqual(0);
+            expect(stats.peakLoad).toBeGreaterThanOrEqual(stats.averageLoad);
+            expect(Number.isInteger(stats.underruns)).toStrictEqual(true);
+            expect(Number.isInteger(stats.overruns)).toStrictEqual(true);
+            expect(Number.isInteger(stats.bufferSize)).toStrictEqual(true);
+        });
+
+        test("onXrun()", () => {
+            const watcher = engine.onXrun(() => {}, { interval: 10 });
+            expect(watcher.stop()).toBeUndefined();
+            expect(() => engine.onXrun(() => {}, { interval: 0 })).toThrow(
+                RangeError,
+            );
+        });
+
+        test("record() requires input device", () => {
+            expect(engine.isRecording()).toStrictEqual(false);
+            expect(() => engine.record(__dirname)).toThrow();
+            expect(engine.isRecording()).toStrictEqual(false);
+        });
+
+        test("stopRecording() compensates for latency", async () => {
+            const recordingEngine = Engine.getDummy({ input: true });
+            const track = recordingEngine.addAudioTrack();
+            track.setRecordArmed(true);
+
+            recordingEngine.record(__dirname);
+            recordingEngine.play();
+            await new Promise((resolve) => setTimeout(resolve, 500));
+            recordingEngine.pause();
+            const { clips, errors } = recordingEngine.stopRecording();
+
+            expect(errors).toStrictEqual([]);
+            expect(clips.length).toStrictEqual(1);
+            const latency = recordingEngine.getLatency();
+            const compensation = recordingEngine.fromSamples(
+                latency.input + latency.output + latency.processing,
+            );
+            expect(clips[0].getSourceOffset().getBeatUnits()).toStrictEqual(
+                compensation.getBeatUnits(),
+            );
+            recordingEngine.close();
+        });
+
+        test("suspend() and resume()", () => {
+            const track = engine.addAudioTrack();
+            const clip = track.addClip(importTestClip(), Timestamp.zero());
+            expect(engine.isSuspended()).toStrictEqual(false);
+
+            engine.suspend();
+            expect(engine.isSuspended()).toStrictEqual(true);
+            // Everything stays usable while suspended
+            expect(track.getClips().length).toStrictEqual(1);
+            expect(clip.getStart().getBeatUnits()).toStrictEqual(0);
+            engine.suspend();
+            expect(engine.isSuspended()).toStrictEqual(true);
+
+            engine.resume();
+            expect(engine.isSuspended()).toStrictEqual(false);
+            expect(track.getClips().length).toStrictEqual(1);
+        });
+
         test("jumpTo()", () => {
             expect(engine.jumpTo(Timestamp.zero())).toBeUndefined();
         });
@@ -64,6 +146,441 @@ describe("Engine", () => {
         test("getPlayheadPosition()", () => {
             expect(engine.getPlayheadPosition()).toBeDefined();
         });
+
+        test("setPunch()", () => {
+            expect(engine.getPunch()).toBeNull();
+
+            engine.setPunch(Timestamp.fromBeats(1), Timestamp.fromBeats(3));
+            const punch = engine.getPunch();
+            expect(punch?.start.getBeats()).toStrictEqual(1);
+            expect(punch?.end.getBeats()).toStrictEqual(3);
+
+            engine.clearPunch();
+            expect(engine.getPunch()).toBeNull();
+        });
+
+        test("setLoop()", () => {
+            expect(engine.getLoop()).toBeNull();
+
+            engine.setLoop(Timestamp.fromBeats(4), Timestamp.fromBeats(8));
+            const loop = engine.getLoop();
+            expect(loop?.start.getBeats()).toStrictEqual(4);
+            expect(loop?.end.getBeats()).toStrictEqual(8);
+
+            engine.clearLoop();
+            expect(engine.getLoop()).toBeNull();
+        });
+
+        test("Reversed ranges are rejected", () => {
+            const a = Timestamp.fromBeats(1);
+            const b = Timestamp.fromBeats(2);
+            for (const method of [
+                "setPunch",
+                "setLoop",
+                "getClipsInRange",
+                "removeTime",
+                "copyTime",
+                "cutTime",
+            ] as const) {
+                expect(() => engine[method](b, a)).toThrow(RangeError);
+            }
+        });
+
+        test("Empty punch and loop ranges clear them", () => {
+            const a = Timestamp.fromBeats(1);
+            const b = Timestamp.fromBeats(2);
+            engine.setPunch(a, b);
+            engine.setPunch(a, a);
+            expect(engine.getPunch()).toBeNull();
+            engine.setLoop(a, b);
+            engine.setLoop(a, a);
+            expect(engine.getLoop()).toBeNull();
+        });
+
+        test("getClipsInRange()", () => {
+            const storedClip = importTestClip();
+            const track1 = engine.addAudioTrack();
+            const track2 = engine.addAudioTrack();
+            const a = track1.addClip(
+                storedClip,
+                Timestamp.fromBeats(0),
+                Timestamp.fromBeats(2),
+            );
+            const b = track2.addClip(
+                storedClip,
+                Timestamp.fromBeats(3),
+                Timestamp.fromBeats(2),
+            );
+            track1.addClip(
+                storedClip,
+                Timestamp.fromBeats(10),
+                Timestamp.fromBeats(2),
+            );
+
+            const keys = (clips: AudioClip[]) =>
+                clips.map(c => c.getKey()).sort();
+
+            expect(
+                keys(
+                    engine.getClipsInRange(
+                        Timestamp.fromBeats(1),
+                        Timestamp.fromBeats(4),
+                    ),
+                ),
+            ).toStrictEqual(keys([a, b]));
+            expect(
+                keys(
+                    engine.getClipsInRange(
+                        Timestamp.fromBeats(1),
+                        Timestamp.fromBeats(4),
+                        { tracks: [track2] },
+                    ),
+                ),
+            ).toStrictEqual(keys([b]));
+            for (const options of [null, { tracks: null }]) {
+                expect(
+                    keys(
+                        engine.getClipsInRange(
+                            Timestamp.fromBeats(1),
+                            Timestamp.fromBeats(4),
+                            options,
+                        ),
+                    ),
+                ).toStrictEqual(keys([a, b]));
+            }
+            expect(
+                engine.getClipsInRange(
+                    Timestamp.fromBeats(2),
+                    Timestamp.fromBeats(3),
+                ),
+            ).toStrictEqual([]);
+        });
+
+        test("toSamples() and fromSamples()", () => {
+            expect(engine.toSamples(Timestamp.zero())).toStrictEqual(0);
+
+            const timestamp = Timestamp.fromBeats(4);
+            const samples = engine.toSamples(timestamp);
+            expect(samples).toBeGreaterThan(0);
+            expect(engine.fromSamples(samples).getBeats()).toStrictEqual(4);
+            expect(() => engine.fromSamples(-1)).toThrow(RangeError);
+        });
+
+        test("toSamples() uses the output sample rate", () => {
+            const timestamp = Timestamp.fromBeats(4);
+            const sampleRate = engine.getConfig().getOutputConfig().sampleRate;
+            const seconds = engine.toSeconds(timestamp);
+            expect(engine.toSamples(timestamp)).toBeCloseTo(
+                seconds * sampleRate,
+                -1,
+            );
+        });
+
+        test("toSeconds() and fromSeconds()", () => {
+            const timestamp = Timestamp.fromBeats(4);
+            const seconds = engine.toSeconds(timestamp);
+            expect(seconds).toBeGreaterThan(0);
+            expect(engine.fromSeconds(seconds).getBeatUnits()).toStrictEqual(
+                timestamp.getBeatUnits(),
+            );
+            expect(() => engine.fromSeconds(-1)).toThrow(RangeError);
+        });
+
+        describe("Ripple editing", () => {
+            let track: AudioTrack;
+            let before: AudioClip;
+            let straddling: AudioClip;
+            let after: AudioClip;
+
+            beforeEach(() => {
+                const storedClip = importTestClip();
+                track = engine.addAudioTrack();
+                before = track.addClip(
+                    storedClip,
+                    Timestamp.fromBeats(0),
+                    Timestamp.fromBeats(2),
+                );
+                straddling = track.addClip(
+                    storedClip,
+                    Timestamp.fromBeats(3),
+                    Timestamp.fromBeats(4),
+                );
+                after = track.addClip(
+                    storedClip,
+                    Timestamp.fromBeats(10),
+                    Timestamp.fromBeats(2),
+                );
+            });
+
+            function starts() {
+                return track
+                    .getClips()
+                    .map(c => c.getStart().getBeats())
+                    .sort((a, b) => a - b);
+            }
+
+            test("insertTime()", () => {
+                engine.insertTime(
+                    Timestamp.fromBeats(5),
+                    Timestamp.fromBeats(3),
+                );
+
+                expect(starts()).toStrictEqual([0, 3, 8, 13]);
+                expect(straddling.getLength().getBeats()).toStrictEqual(2);
+                expect(before.getStart().getBeats()).toStrictEqual(0);
+            });
+
+            test("insertTime() on selected tracks", () => {
+                const other = engine.addAudioTrack();
+                const otherClip = other.addClip(
+                    importTestClip(),
+                    Timestamp.fromBeats(10),
+                    Timestamp.fromBeats(1),
+                );
+
+                engine.insertTime(
+                    Timestamp.fromBeats(5),
+                    Timestamp.fromBeats(3),
+                    { tracks: [track] },
+                );
+
+                expect(after.getStart().getBeats()).toStrictEqual(13);
+                expect(otherClip.getStart().getBeats()).toStrictEqual(10);
+            });
+
+            test("insertTime() with zero length does nothing", () => {
+                const state = engine.insertTime(
+                    Timestamp.fromBeats(4),
+                    Timestamp.zero(),
+                );
+
+                expect(track.getClips().length).toStrictEqual(3);
+                expect(straddling.getLength().getBeats()).toStrictEqual(4);
+
+                engine.revertTimelineEdit(state);
+                expect(starts()).toStrictEqual([0, 3, 10]);
+            });
+
+            test("insertTime() failure leaves all tracks unchanged", () => {
+                const other = engine.addAudioTrack();
+                const otherClip = other.addClip(
+                    importTestClip(),
+                    Timestamp.fromBeats(1),
+                    Timestamp.fromBeats(1),
+                );
+
+                // The other track is edited first, and moving `after` on this track overflows.
+                expect(() =>
+                    engine.insertTime(
+                        Timestamp.zero(),
+                        Timestamp.sub(
+                            Timestamp.infinity(),
+                            Timestamp.fromBeats(5),
+                        ),
+                        { tracks: [other, track] },
+                    ),
+                ).toThrow(RangeError);
+
+                expect(otherClip.getStart().getBeats()).toStrictEqual(1);
+                expect(starts()).toStrictEqual([0, 3, 10]);
+            });
+
+            test("removeTime()", () => {
+                engine.removeTime(
+                    Timestamp.fromBeats(1),
+                    Timestamp.fromBeats(4),
+                );
+
+                expect(starts()).toStrictEqual([0, 1, 7]);
+                expect(before.getLength().getBeats()).toStrictEqual(1);
+                expect(straddling.getLength().getBeats()).toStrictEqual(3);
+                expect(after.getStart().getBeats()).toStrictEqual(7);
+            });
+
+            test("removeTime() deletes clips within range", () => {
+                engine.removeTime(
+                    Timestamp.fromBeats(2),
+                    Timestamp.fromBeats(8),
+                );
+
+                expect(starts()).toStrictEqual([0, 4]);
+            });
+
+            test("revertTimelineEdit()", () => {
+                const state = engine.removeTime(
+                    Timestamp.fromBeats(4),
+                    Timestamp.fromBeats(5),
+                );
+                expect(track.getClips().length).toStrictEqual(4);
+
+                engine.revertTimelineEdit(state);
+
+                expect(starts()).toStrictEqual([0, 3, 10]);
+                const restored = track
+                    .getClips()
+                    .find(c => c.getKey() === straddling.getKey());
+                expect(restored.getLength().getBeats()).toStrictEqual(4);
+            });
+        });
+
+        describe("Clipboard", () => {
+            let track: AudioTrack;
+            let other: AudioTrack;
+            let before: AudioClip;
+            let straddling: AudioClip;
+
+            beforeEach(() => {
+                const storedClip = importTestClip();
+                track = engine.addAudioTrack();
+                other = engine.addAudioTrack();
+                before = track.addClip(
+                    storedClip,
+                    Timestamp.fromBeats(0),
+                    Timestamp.fromBeats(2),
+                );
+                straddling = track.addClip(
+                    storedClip,
+                    Timestamp.fromBeats(3),
+                    Timestamp.fromBeats(4),
+                );
+            });
+
+            test("copyTime() crops clips to the range", () => {
+                const clipboard = engine.copyTime(
+                    Timestamp.fromBeats(1),
+                    Timestamp.fromBeats(4),
+                    { tracks: [track] },
+                );
+                const beat = Timestamp.fromBeats(1).getBeatUnits();
+
+                expect(clipboard.length).toStrictEqual(3 * beat);
+                expect(clipboard.tracks.length).toStrictEqual(1);
+                const clips = clipboard.tracks[0];
+                expect(clips.map(c => c.start)).toStrictEqual([0, 2 * beat]);
+                expect(clips.map(c => c.length)).toStrictEqual([beat, beat]);
+                expect(clips[0].sourceOffset).toStrictEqual(beat);
+                expect(clips[0].storedClip).toStrictEqual(
+                    before.getStoredClip().getKey(),
+                );
+
+                expect(before.getLength().getBeats()).toStrictEqual(2);
+                expect(straddling.getLength().getBeats()).toStrictEqual(4);
+            });
+
+            test("paste() onto another track", () => {
+                const clipboard = engine.copyTime(
+                    Timestamp.fromBeats(1),
+                    Timestamp.fromBeats(4),
+                    { tracks: [track] },
+                );
+                const serialized = JSON.parse(JSON.stringify(clipboard));
+
+                const pasted = engine.paste(
+                    serialized,
+                    Timestamp.fromBeats(20),
+                    [other],
+                );
+
+                const starts = pasted.map(c => c.getStart().getBeats());
+                expect(starts).toStrictEqual([20, 22]);
+                const lengths = pasted.map(c => c.getLength().getBeats());
+                expect(lengths).toStrictEqual([1, 1]);
+                expect(pasted[0].getSourceOffset().getBeats()).toStrictEqual(1);
+                expect(pasted[0].getTrack().getKey()).toStrictEqual(
+                    other.getKey(),
+                );
+            });
+
+            test("paste() rejects overlap without changing anything", () => {
+                const clipboard = engine.copyTime(
+                    Timestamp.fromBeats(0),
+                    Timestamp.fromBeats(4),
+                    { tracks: [track] },
+                );
+
+                expect(() =>
+                    engine.paste(clipboard, Timestamp.fromBeats(1), [track]),
+                ).toThrow();
+                expect(track.getClips().length).toStrictEqual(2);
+            });
+
+            test("paste() failure on one track leaves all tracks unchanged", () => {
+                const clipboard = engine.copyTime(
+                    Timestamp.fromBeats(0),
+                    Timestamp.fromBeats(4),
+                    { tracks: [track] },
+                );
+                const [clips] = clipboard.tracks;
+                // The stored clip of the second track does not exist, so pasting it fails.
+                clipboard.tracks.push([{ ...clips[0], storedClip: 999999 }]);
+
+                expect(() =>
+                    engine.paste(
+                        clipboard,
+                        Timestamp.fromBeats(1),
+                        [track, other],
+                        OverlapMode.Trim,
+                    ),
+                ).toThrow();
+
+                expect(track.getClips().length).toStrictEqual(2);
+                expect(before.getLength().getBeats()).toStrictEqual(2);
+                expect(straddling.getStart().getBeats()).toStrictEqual(3);
+                expect(straddling.getLength().getBeats()).toStrictEqual(4);
+                expect(other.getClips().length).toStrictEqual(0);
+            });
+
+            test("paste() requires matching number of tracks", () => {
+                const clipboard = engine.copyTime(
+                    Timestamp.fromBeats(0),
+                    Timestamp.fromBeats(4),
+                    { tracks: [track] },
+                );
+
+                expect(() =>
+                    engine.paste(clipboard, Timestamp.fromBeats(20), [
+                        track,
+                        other,
+                    ]),
+                ).toThrow();
+            });
+
+            test("cutTime() and revertTimelineEdit()", () => {
+                const { clipboard, state } = engine.cutTime(
+                    Timestamp.fromBeats(1),
+                    Timestamp.fromBeats(4),
+                    { tracks: [track] },
+                );
+
+                expect(clipboard.tracks[0].length).toStrictEqual(2);
+                const starts = track
+                    .getClips()
+                    .map(c => c.getStart().getBeats())
+                    .sort((a, b) => a - b);
+                expect(starts).toStrictEqual([0, 4]);
+                expect(before.getLength().getBeats()).toStrictEqual(1);
+
+                engine.revertTimelineEdit(state);
+
+                expect(track.getClips().length).toStrictEqual(2);
+                expect(before.getLength().getBeats()).toStrictEqual(2);
+            });
+        });
+
+        test("getClipsInRange() with empty range", () => {
+            const track = engine.addAudioTrack();
+            const clip = track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(1),
+                Timestamp.fromBeats(2),
+            );
+
+            const result = engine.getClipsInRange(
+                Timestamp.fromBeats(1),
+                Timestamp.fromBeats(1),
+            );
+            expect(result.map(c => c.getKey())).toStrictEqual([clip.getKey()]);
+        });
     });
 
     describe("Mixer", () => {
@@ -242,8 +759,16 @@ describe("Engine", () => {
                 expect(() => engine.getConfig()).toThrow(msg);
                 expect(() => engine.play()).toThrow(msg);
                 expect(() => engine.pause()).toThrow(msg);
+                expect(() => engine.suspend()).toThrow(msg);
+                expect(() => engine.resume()).toThrow(msg);
+                expect(() => engine.isSuspended()).toThrow(msg);
+                expect(() => engine.getRealtimePriority()).toThrow(msg);
                 expect(() => engine.jumpTo(Timestamp.zero())).toThrow(msg);
                 expect(() => engine.getPlayheadPosition()).toThrow(msg);
+                expect(() => engine.toSamples(Timestamp.zero())).toThrow(msg);
+                expect(() => engine.fromSamples(0)).toThrow(msg);
+                expect(() => engine.toSeconds(Timestamp.zero())).toThrow(msg);
+                expect(() => engine.fromSeconds(0)).toThrow(msg);
                 expect(() => engine.getMaster()).toThrow(msg);
                 expect(() => engine.getAudioTracks()).toThrow(msg);
                 expect(() => engine.addAudioTrack()).toThrow(msg);
@@ -254,7 +779,34 @@ describe("Engine", () => {
                     msg,
                 );
                 expect(() => engine.reconstructAudioTracks([])).toThrow(msg);
+                expect(() =>
+                    engine.getClipsInRange(Timestamp.zero(), Timestamp.zero()),
+                ).toThrow(msg);
+                expect(() =>
+                    engine.copyTime(Timestamp.zero(), Timestamp.zero()),
+                ).toThrow(msg);
+                expect(() =>
+                    engine.cutTime(Timestamp.zero(), Timestamp.zero()),
+                ).toThrow(msg);
+                const clipboard = { length: 0, tracks: [] };
+                expect(() =>
+                    engine.paste(clipboard, Timestamp.zero(), []),
+                ).toThrow(msg);
                 expect(() => engine.importAudioClip("...")).toThrow(msg);
+                expect(() => engine.record("...")).toThrow(msg);
+                expect(() => engine.isRecording()).toThrow(msg);
+                expect(() => engine.getLatency()).toThrow(msg);
+                expect(() => engine.getStats()).toThrow(msg);
+                expect(() => engine.onXrun(() => {})).toThrow(msg);
+                expect(() => engine.stopRecording()).toThrow(msg);
+                expect(() => engine.getPunch()).toThrow(msg);
+                expect(() => engine.clearPunch()).toThrow(msg);
+                expect(() => engine.getLoop()).toThrow(msg);
+                expect(() => engine.clearLoop()).toThrow(msg);
+                expect(() => engine.onOutputDeviceLost(() => {})).toThrow(msg);
+                expect(() =>
+                    engine.enableFailover(FailoverTarget.Dummy, () => {}),
+                ).toThrow(msg);
             });
         });
 
@@ -353,6 +905,133 @@ describe("Engine", () => {
                     }
                 });
 
+                test("crossfade()", () => {
+                    const storedClip = importTestClip();
+                    const left = track.addClip(
+                        storedClip,
+                        Timestamp.zero(),
+                        Timestamp.fromBeats(2),
+                    );
+                    const right = track.addClip(
+                        storedClip,
+                        Timestamp.fromBeats(2),
+                        Timestamp.fromBeats(2),
+                    );
+
+                    track.crossfade(left, right, Timestamp.fromBeats(1));
+
+                    expect(left.getLength().getBeats()).toStrictEqual(3);
+                    expect(left.getFadeOut().length.getBeats()).toStrictEqual(1);
+                    expect(left.getFadeOut().curve).toStrictEqual(
+                        FadeCurve.EqualPower,
+                    );
+                    expect(right.getStart().getBeats()).toStrictEqual(2);
+                    expect(right.getFadeIn().length.getBeats()).toStrictEqual(1);
+                });
+
+                test("crossfade() of separated clips throws", () => {
+                    const storedClip = importTestClip();
+                    const left = track.addClip(
+                        storedClip,
+                        Timestamp.zero(),
+                        Timestamp.fromBeats(1),
+                    );
+                    const right = track.addClip(
+                        storedClip,
+                        Timestamp.fromBeats(2),
+                        Timestamp.fromBeats(1),
+                    );
+
+                    expect(() =>
+                        track.crossfade(left, right, Timestamp.fromBeats(1)),
+                    ).toThrow();
+                });
+
+                test("duplicate()", () => {
+                    const storedClip = importTestClip();
+                    track.addClip(storedClip, Timestamp.zero());
+                    track.addClip(
+                        storedClip,
+                        Timestamp.fromBeats(100),
+                        Timestamp.fromBeats(1),
+                    );
+                    track.setVolume(0.5);
+                    track.setPanning(-0.5);
+                    track.setRecordArmed(true);
+                    track.setInputChannels([1]);
+                    track.setMonitorMode(MonitorMode.Always);
+
+                    const copy = track.duplicate();
+
+                    expect(copy.getKey()).not.toStrictEqual(track.getKey());
+                    expect(copy.getVolume()).toStrictEqual(0.5);
+                    expect(copy.getPanning()).toStrictEqual(-0.5);
+                    expect(copy.isRecordArmed()).toStrictEqual(false);
+                    expect(copy.getInputChannels()).toStrictEqual([1]);
+                    expect(copy.getMonitorMode()).toStrictEqual(
+                        MonitorMode.Always,
+                    );
+
+                    const clips = track.getClips();
+                    const copiedClips = copy.getClips();
+                    expect(copiedClips.length).toStrictEqual(clips.length);
+                    for (let i = 0; i < clips.length; i++) {
+                        expect(
+                            Timestamp.eq(
+                                copiedClips[i].getStart(),
+                                clips[i].getStart(),
+                            ),
+                        ).toStrictEqual(true);
+                        expect(
+                            Timestamp.eq(
+                                copiedClips[i].getLength(),
+                                clips[i].getLength(),
+                            ),
+                        ).toStrictEqual(true);
+                    }
+                });
+
+                test("setMonitorMode()", () => {
+                    expect(track.getMonitorMode()).toStrictEqual(
+                        MonitorMode.Off,
+                    );
+                    for (const mode of [
+                        MonitorMode.Auto,
+                        MonitorMode.Always,
+                        MonitorMode.Off,
+                    ]) {
+                        track.setMonitorMode(mode);
+                        expect(track.getMonitorMode()).toStrictEqual(mode);
+                    }
+                });
+
+                test("setRecordArmed()", () => {
+                    expect(track.isRecordArmed()).toStrictEqual(false);
+                    track.setRecordArmed(true);
+                    expect(track.isRecordArmed()).toStrictEqual(true);
+                    track.setRecordArmed(false);
+                    expect(track.isRecordArmed()).toStrictEqual(false);
+                });
+
+                test("setInputChannels()", () => {
+                    track.setInputChannels([0, 1]);
+                    expect(track.getInputChannels()).toStrictEqual([0, 1]);
+                    track.setInputChannels([1]);
+                    expect(track.getInputChannels()).toStrictEqual([1]);
+                });
+
+                test("setInputChannels() rejects invalid channels", () => {
+                    expect(() => track.setInputChannels([])).toThrow(
+                        RangeError,
+                    );
+                    expect(() => track.setInputChannels([-1])).toThrow(
+                        RangeError,
+                    );
+                    expect(() => track.setInputChannels([0.5])).toThrow(
+                        RangeError,
+                    );
+                });
+
                 test("delete() deletes track", () => {
                     const key = track.getKey();
                     track.delete();
@@ -388,6 +1067,16 @@ describe("Engine", () => {
                     expect(() => track.deleteClips([])).toThrow(msg);
                     expect(() => track.reconstructClip(clipState)).toThrow(msg);
                     expect(() => track.reconstructClips([])).toThrow(msg);
+                    expect(() => track.duplicate()).toThrow(msg);
+                    expect(() => track.getTakeRegions()).toThrow(msg);
+                    expect(() => track.getMonitorMode()).toThrow(msg);
+                    expect(() => track.setMonitorMode(MonitorMode.Off)).toThrow(
+                        msg,
+                    );
+                    expect(() => track.isRecordArmed()).toThrow(msg);
+                    expect(() => track.setRecordArmed(true)).toThrow(msg);
+                    expect(() => track.getInputChannels()).toThrow(msg);
+                    expect(() => track.setInputChannels([0])).toThrow(msg);
                     expect(() => track.delete()).toThrow(msg);
                 });
             });
@@ -492,6 +1181,32 @@ describe("Engine", () => {
             expect(clip.getStart().getBeats()).toStrictEqual(2);
         });
 
+        test("nudge()", () => {
+            clip.nudge(Offset.fromBeats(2));
+            expect(clip.getStart().getBeats()).toStrictEqual(3);
+
+            clip.nudge(Offset.fromBeats(-2.5));
+            expect(clip.getStart().getBeatUnits()).toStrictEqual(512);
+        });
+
+        test("nudge() before start of timeline throws", () => {
+            expect(() => clip.nudge(Offset.fromBeats(-2))).toThrow(RangeError);
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+        });
+
+        test("nudge() overlap throws OverlapError", () => {
+            track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(4),
+                Timestamp.fromBeats(2),
+            );
+
+            expect(() => clip.nudge(Offset.fromBeats(2))).toThrow();
+            const removed = clip.nudge(Offset.fromBeats(2), OverlapMode.Trim);
+            expect(removed).toStrictEqual([]);
+            expect(clip.getStart().getBeats()).toStrictEqual(3);
+        });
+
         test("moveToTrack()", () => {
             const track2 = engine.addAudioTrack();
 
@@ -515,6 +1230,231 @@ describe("Engine", () => {
             ).toThrow();
         });
 
+        test("moveToTrack() with OverlapMode.Replace", () => {
+            const track2 = engine.addAudioTrack();
+            const other = track2.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(2),
+                Timestamp.fromBeats(1),
+            );
+
+            const removed = clip.moveToTrack(
+                Timestamp.fromBeats(2),
+                track2,
+                OverlapMode.Replace,
+            );
+
+            expect(removed.length).toStrictEqual(1);
+            expect(track.getClips().length).toStrictEqual(0);
+            expect(track2.getClips().map(c => c.getKey())).toStrictEqual([
+                clip.getKey(),
+            ]);
+            expect(() => other.getStart()).toThrow();
+        });
+
+        test("getTrack()", () => {
+            expect(clip.getTrack().getKey()).toStrictEqual(track.getKey());
+
+            const track2 = engine.addAudioTrack();
+            clip.moveToTrack(Timestamp.fromBeats(2), track2);
+
+            expect(clip.getTrack().getKey()).toStrictEqual(track2.getKey());
+        });
+
+        test("move() overlap throws OverlapError", () => {
+            const other = track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(4),
+                Timestamp.fromBeats(2),
+            );
+
+            let error: OverlapError;
+            try {
+                clip.move(Timestamp.fromBeats(3));
+            } catch (e) {
+                error = e;
+            }
+            expect(error.name).toStrictEqual("OverlapError");
+            expect(error.clips.map(c => c.getKey())).toStrictEqual([
+                other.getKey(),
+            ]);
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+        });
+
+        test("move() onto itself", () => {
+            expect(clip.move(Timestamp.fromBeats(2))).toStrictEqual([]);
+            expect(clip.getStart().getBeats()).toStrictEqual(2);
+        });
+
+        test("move() with OverlapMode.Trim", () => {
+            const other = track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(4),
+                Timestamp.fromBeats(2),
+            );
+
+            clip.move(Timestamp.fromBeats(3), OverlapMode.Trim);
+
+            expect(other.getStart().getBeats()).toStrictEqual(5);
+            expect(other.getLength().getBeats()).toStrictEqual(1);
+        });
+
+        test("addClip() overlap throws OverlapError", () => {
+            let error: OverlapError;
+            try {
+                track.addClip(
+                    importTestClip(),
+                    Timestamp.fromBeats(2),
+                    Timestamp.fromBeats(1),
+                );
+            } catch (e) {
+                error = e;
+            }
+            expect(error.name).toStrictEqual("OverlapError");
+            expect(error.clips.map(c => c.getKey())).toStrictEqual([
+                clip.getKey(),
+            ]);
+            expect(track.getClips().length).toStrictEqual(1);
+        });
+
+        test("addClip() with OverlapMode.Crossfade", () => {
+            const newClip = track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(2),
+                Timestamp.fromBeats(4),
+                OverlapMode.Crossfade,
+            );
+
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(2);
+            expect(clip.getFadeOut().length.getBeats()).toStrictEqual(1);
+            expect(clip.getFadeOut().curve).toStrictEqual(FadeCurve.EqualPower);
+
+            expect(newClip.getStart().getBeats()).toStrictEqual(2);
+            expect(newClip.getLength().getBeats()).toStrictEqual(4);
+            expect(newClip.getFadeIn().length.getBeats()).toStrictEqual(1);
+        });
+
+        test("addClip() with OverlapMode.Replace", () => {
+            track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(2),
+                Timestamp.fromBeats(4),
+                OverlapMode.Replace,
+            );
+
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(1);
+            expect(track.getClips().length).toStrictEqual(2);
+        });
+
+        test("placeClip() with OverlapMode.Replace can be undone", () => {
+            const { clip: newClip, removed } = track.placeClip(
+                importTestClip(),
+                Timestamp.fromBeats(2),
+                Timestamp.fromBeats(4),
+                OverlapMode.Replace,
+            );
+
+            expect(removed.length).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(1);
+
+            newClip.delete();
+            const restored = track.reconstructClips(removed);
+            expect(restored[0].getStart().getBeats()).toStrictEqual(2);
+            expect(restored[0].getLength().getBeats()).toStrictEqual(1);
+        });
+
+        test("placeClip() with OverlapMode.Replace inside clip", () => {
+            const { removed } = track.placeClip(
+                importTestClip(),
+                Timestamp.fromBeatUnits(1024 + 256),
+                Timestamp.fromBeatUnits(512),
+                OverlapMode.Replace,
+            );
+
+            expect(removed.length).toStrictEqual(1);
+            expect(track.getClips().length).toStrictEqual(3);
+        });
+
+        test("placeClip() with OverlapMode.Trim inside clip crops without deleting", () => {
+            const { clip: newClip, removed } = track.placeClip(
+                importTestClip(),
+                Timestamp.fromBeatUnits(1024 + 256),
+                Timestamp.fromBeatUnits(512),
+                OverlapMode.Trim,
+            );
+
+            expect(removed).toStrictEqual([]);
+            expect(clip.getStart().getBeatUnits()).toStrictEqual(1024);
+            expect(clip.getLength().getBeatUnits()).toStrictEqual(256);
+
+            const clips = track.getClips();
+            expect(clips.length).toStrictEqual(3);
+            const tail = clips.find(
+                c =>
+                    c.getKey() !== clip.getKey() &&
+                    c.getKey() !== newClip.getKey(),
+            );
+            expect(tail.getStart().getBeatUnits()).toStrictEqual(1024 + 768);
+            expect(tail.getLength().getBeatUnits()).toStrictEqual(1280);
+        });
+
+        test("addClip() with OverlapMode.Trim rejects covering a clip entirely", () => {
+            let error: OverlapError;
+            try {
+                track.addClip(
+                    importTestClip(),
+                    Timestamp.zero(),
+                    Timestamp.fromBeats(4),
+                    OverlapMode.Trim,
+                );
+            } catch (e) {
+                error = e;
+            }
+            expect(error.name).toStrictEqual("OverlapError");
+            expect(error.clips.map(c => c.getKey())).toStrictEqual([
+                clip.getKey(),
+            ]);
+            expect(track.getClips().length).toStrictEqual(1);
+        });
+
+        test("addClip() without overlap mode rejects", () => {
+            expect(() =>
+                track.addClip(
+                    importTestClip(),
+                    Timestamp.fromBeats(2),
+                    Timestamp.fromBeats(1),
+                    undefined,
+                ),
+            ).toThrow();
+            expect(track.getClips().length).toStrictEqual(1);
+        });
+
+        test("addClip() leaves clips in place on failure", () => {
+            // The stored clip is shorter than this, so adding it fails after making room.
+            expect(() =>
+                track.addClip(
+                    importTestClip(),
+                    Timestamp.fromBeats(2),
+                    Timestamp.fromBeats(100),
+                    OverlapMode.Trim,
+                ),
+            ).toThrow();
+
+            expect(track.getClips().length).toStrictEqual(1);
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(2);
+        });
+
+        test("addClip() return type does not depend on arguments", () => {
+            const newClip = track.addClip(
+                importTestClip(),
+                Timestamp.fromBeats(4),
+            );
+            expect(newClip.getStart().getBeats()).toStrictEqual(4);
+        });
+
         test("cropStart()", () => {
             clip.cropStart(Timestamp.fromBeats(1));
             expect(clip.getStart().getBeats()).toStrictEqual(2);
@@ -527,10 +1467,180 @@ describe("Engine", () => {
             expect(clip.getLength().getBeats()).toStrictEqual(1);
         });
 
+        test("getSourceOffset()", () => {
+            expect(clip.getSourceOffset().getBeatUnits()).toStrictEqual(0);
+            clip.cropStart(Timestamp.fromBeats(1));
+            expect(clip.getSourceOffset().getBeats()).toStrictEqual(1);
+        });
+
+        test("slip()", () => {
+            clip.slip(Offset.fromBeats(-2));
+            expect(clip.getSourceOffset().getBeats()).toStrictEqual(2);
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(2);
+
+            clip.slip(Offset.fromBeats(1));
+            expect(clip.getSourceOffset().getBeats()).toStrictEqual(1);
+            expect(clip.getStart().getBeats()).toStrictEqual(1);
+        });
+
+        test("slip() before start of stored clip throws", () => {
+            expect(() => clip.slip(Offset.fromBeats(1))).toThrow(
+                /forwards past the start/,
+            );
+            expect(clip.getSourceOffset().getBeatUnits()).toStrictEqual(0);
+        });
+
+        test("slip() past end of stored clip throws", () => {
+            // The stored clip is 55 beats long, and the clip is 2 beats long.
+            clip.slip(Offset.fromBeats(-53));
+            expect(clip.getSourceOffset().getBeats()).toStrictEqual(53);
+
+            expect(() => clip.slip(Offset.fromBeats(-1))).toThrow(
+                /backwards past the end/,
+            );
+            expect(clip.getSourceOffset().getBeats()).toStrictEqual(53);
+        });
+
         test("getStoredClip()", () => {
             expect(clip.getStoredClip()).toBeDefined();
         });
 
+        test("Clip that was not loop recorded has a single take", () => {
+            const takes = clip.getTakes();
+            expect(takes.length).toStrictEqual(1);
+            expect(takes[0].getKey()).toStrictEqual(
+                clip.getStoredClip().getKey(),
+            );
+            expect(clip.getActiveTake()).toStrictEqual(0);
+            expect(track.getTakeRegions()).toStrictEqual([]);
+        });
+
+        test("setActiveTake()", () => {
+            expect(clip.setActiveTake(0)).toBeUndefined();
+            expect(() => clip.setActiveTake(1)).toThrow(RangeError);
+            expect(() => clip.setActiveTake(-1)).toThrow(RangeError);
+            expect(() => clip.setActiveTake(0.5)).toThrow(RangeError);
+        });
+
+        test("split()", () => {
+            clip.setGain(0.5);
+            const { left, right } = clip.split(Timestamp.fromBeats(2));
+
+            expect(left.getKey()).toStrictEqual(clip.getKey());
+            expect(right.getKey()).not.toStrictEqual(clip.getKey());
+
+            expect(left.getStart().getBeats()).toStrictEqual(1);
+            expect(left.getLength().getBeats()).toStrictEqual(1);
+            expect(right.getStart().getBeats()).toStrictEqual(2);
+            expect(right.getLength().getBeats()).toStrictEqual(1);
+            expect(right.getGain()).toStrictEqual(0.5);
+
+            expect(track.getClips().length).toStrictEqual(2);
+        });
+
+        test("split() outside clip throws", () => {
+            expect(() => clip.split(Timestamp.fromBeats(1))).toThrow();
+            expect(() => clip.split(Timestamp.fromBeats(4))).toThrow();
+        });
+
+        test("split() can be undone", () => {
+            const { state } = clip.split(Timestamp.fromBeats(2));
+            engine.revertTimelineEdit(state);
+
+            const clips = track.getClips();
+            expect(clips.length).toStrictEqual(1);
+            expect(clips[0].getKey()).toStrictEqual(clip.getKey());
+            expect(clips[0].getStart().getBeats()).toStrictEqual(1);
+            expect(clips[0].getLength().getBeats()).toStrictEqual(2);
+        });
+
+        test("split() can be undone after the right part was moved or deleted", () => {
+            const first = clip.split(Timestamp.fromBeats(2));
+            first.right.move(Timestamp.fromBeats(10));
+            engine.revertTimelineEdit(first.state);
+            expect(track.getClips().length).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(2);
+
+            const second = clip.split(Timestamp.fromBeats(2));
+            track.deleteClips([second.right]);
+            engine.revertTimelineEdit(second.state);
+            expect(track.getClips().length).toStrictEqual(1);
+            expect(clip.getLength().getBeats()).toStrictEqual(2);
+        });
+
+        test("duplicate()", () => {
+            clip.cropStart(Timestamp.fromBeats(1));
+            const copy = clip.duplicate(Timestamp.fromBeats(10));
+
+            expect(copy.getKey()).not.toStrictEqual(clip.getKey());
+            expect(copy.getStart().getBeats()).toStrictEqual(10);
+            expect(copy.getLength().getBeats()).toStrictEqual(1);
+            expect(copy.getStoredClip().getKey()).toStrictEqual(
+                clip.getStoredClip().getKey(),
+            );
+            expect(track.getClips().length).toStrictEqual(2);
+        });
+
+        test("duplicate() to other track", () => {
+            const track2 = engine.addAudioTrack();
+            clip.duplicate(Timestamp.fromBeats(1), track2);
+
+            expect(track.getClips().length).toStrictEqual(1);
+            expect(track2.getClips().length).toStrictEqual(1);
+        });
+
+        test("duplicate() overlap throws", () => {
+            expect(() => clip.duplicate(Timestamp.fromBeats(2))).toThrow();
+        });
+
+        test("getGain() returns what's passed to setGain()", () => {
+            expect(clip.getGain()).toStrictEqual(1);
+            clip.setGain(0.5);
+            expect(clip.getGain()).toStrictEqual(0.5);
+        });
+
+        test("getFadeIn() returns what's passed to setFadeIn()", () => {
+            expect(clip.getFadeIn().length.getBeatUnits()).toStrictEqual(0);
+
+            clip.setFadeIn(Timestamp.fromBeatUnits(100), FadeCurve.EqualPower);
+
+            const fade = clip.getFadeIn();
+            expect(fade.length.getBeatUnits()).toStrictEqual(100);
+            expect(fade.curve).toStrictEqual(FadeCurve.EqualPower);
+        });
+
+        test("getFadeOut() returns what's passed to setFadeOut()", () => {
+            clip.setFadeOut(Timestamp.fromBeatUnits(100));
+
+            const fade = clip.getFadeOut();
+            expect(fade.length.getBeatUnits()).toStrictEqual(100);
+            expect(fade.curve).toStrictEqual(FadeCurve.Linear);
+        });
+
+        test("setFadeIn() throws when longer than clip", () => {
+            expect(() => clip.setFadeIn(Timestamp.fromBeats(3))).toThrow();
+        });
+
+        test("Gain and fades survive reconstruction", () => {
+            clip.setGain(0.25);
+            clip.setFadeIn(Timestamp.fromBeatUnits(10), FadeCurve.Exponential);
+            clip.setFadeOut(Timestamp.fromBeatUnits(20));
+
+            const reconstructed = track.reconstructClip(clip.delete());
+
+            expect(reconstructed.getGain()).toStrictEqual(0.25);
+            expect(
+                reconstructed.getFadeIn().length.getBeatUnits(),
+            ).toStrictEqual(10);
+            expect(reconstructed.getFadeIn().curve).toStrictEqual(
+                FadeCurve.Exponential,
+            );
+            expect(
+                reconstructed.getFadeOut().length.getBeatUnits(),
+            ).toStrictEqual(20);
+        });
+
         test("getWaveform()", () => {
             const r = clip.getWaveform();
             // TODO: Use `toBeInstanceOf` when Jest fixes https://github.com/jestjs/jest/issues/11864
@@ -552,6 +1662,7 @@ describe("Engine", () => {
                 "getKey",
                 "getStart",
                 "getLength",
+                "getTrack",
                 "getStoredClip",
                 "delete",
             ];
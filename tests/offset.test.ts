@@ -0,0 +1,78 @@
+import { Offset, Timestamp } from "../index";
+
+test("zero() is zero", () => {
+    expect(Offset.zero().getBeatUnits()).toStrictEqual(0);
+});
+
+test("fromBeatUnits()", () => {
+    expect(Offset.fromBeatUnits(42).getBeatUnits()).toStrictEqual(42);
+    expect(Offset.fromBeatUnits(-42).getBeatUnits()).toStrictEqual(-42);
+    expect(Offset.fromBeatUnits(-42.8).getBeatUnits()).toStrictEqual(-42);
+    expect(() => Offset.fromBeatUnits(2 ** 32)).toThrow(RangeError);
+    expect(() => Offset.fromBeatUnits(-(2 ** 32))).toThrow(RangeError);
+});
+test("fromBeats()", () => {
+    expect(Offset.fromBeats(-1.5).getBeatUnits()).toStrictEqual(-1536);
+    expect(Offset.fromBeats(-1.5).getBeats()).toStrictEqual(-1.5);
+    expect(() => Offset.fromBeats(2 ** 22)).toThrow(RangeError);
+});
+
+test("between()", () => {
+    const timestamp1 = Timestamp.fromBeatUnits(42);
+    const timestamp2 = Timestamp.fromBeatUnits(50);
+    expect(Offset.between(timestamp1, timestamp2).getBeatUnits()).toStrictEqual(
+        8,
+    );
+    expect(Offset.between(timestamp2, timestamp1).getBeatUnits()).toStrictEqual(
+        -8,
+    );
+});
+test("neg()", () => {
+    const offset = Offset.fromBeatUnits(42);
+    expect(Offset.neg(offset).getBeatUnits()).toStrictEqual(-42);
+});
+test("add()", () => {
+    const offset1 = Offset.fromBeatUnits(42);
+    const offset2 = Offset.fromBeatUnits(-50);
+    expect(Offset.add(offset1, offset2).getBeatUnits()).toStrictEqual(-8);
+
+    const max = Offset.fromBeatUnits(2 ** 32 - 1);
+    expect(() => Offset.add(max, max)).toThrow(RangeError);
+});
+test("eq()", () => {
+    const offset1 = Offset.fromBeatUnits(-42);
+    const offset2 = Offset.fromBeatUnits(-42);
+    const offset3 = Offset.fromBeatUnits(42);
+    expect(Offset.eq(offset1, offset2)).toStrictEqual(true);
+    expect(Offset.eq(offset1, offset3)).toStrictEqual(false);
+});
+
+test("apply()", () => {
+    const timestamp = Timestamp.fromBeatUnits(42);
+    const forwards = Offset.fromBeatUnits(8);
+    const backwards = Offset.fromBeatUnits(-8);
+    expect(Offset.apply(timestamp, forwards).getBeatUnits()).toStrictEqual(50);
+    expect(Offset.apply(timestamp, backwards).getBeatUnits()).toStrictEqual(34);
+});
+test("apply() outside timeline throws", () => {
+    const offset = Offset.fromBeatUnits(-50);
+    expect(() => Offset.apply(Timestamp.fromBeatUnits(42), offset)).toThrow(
+        RangeError,
+    );
+    expect(() =>
+        Offset.apply(Timestamp.infinity(), Offset.fromBeatUnits(1)),
+    ).toThrow(RangeError);
+});
+test("apply() with clamping", () => {
+    const timestamp = Timestamp.fromBeatUnits(42);
+    const offset = Offset.fromBeatUnits(-50);
+    expect(Offset.apply(timestamp, offset, true).getBeatUnits()).toStrictEqual(
+        0,
+    );
+    expect(
+        Timestamp.eq(
+            Offset.apply(Timestamp.infinity(), Offset.fromBeatUnits(1), true),
+            Timestamp.infinity(),
+        ),
+    ).toStrictEqual(true);
+});
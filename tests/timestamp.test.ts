@@ -1,4 +1,4 @@
-import { Timestamp } from "../index";
+import { Timestamp, SnapMode } from "../index";
 
 test("min()", () => {
     const timestamp1 = Timestamp.fromBeatUnits(42);
@@ -25,6 +25,29 @@ test("eq() not equals", () => {
     expect(Timestamp.eq(timestamp1, timestamp2)).toStrictEqual(false);
 });
 
+test("cmp()", () => {
+    const timestamp1 = Timestamp.fromBeatUnits(42);
+    const timestamp2 = Timestamp.fromBeatUnits(43);
+    expect(Timestamp.cmp(timestamp1, timestamp2)).toStrictEqual(-1);
+    expect(Timestamp.cmp(timestamp2, timestamp1)).toStrictEqual(1);
+    expect(Timestamp.cmp(timestamp1, timestamp1)).toStrictEqual(0);
+});
+test("cmp() sorts", () => {
+    const timestamps = [43, 1, 42].map(Timestamp.fromBeatUnits);
+    timestamps.sort(Timestamp.cmp);
+    expect(timestamps.map(ts => ts.getBeatUnits())).toStrictEqual([1, 42, 43]);
+});
+test("lt() and gt()", () => {
+    const timestamp1 = Timestamp.fromBeatUnits(42);
+    const timestamp2 = Timestamp.fromBeatUnits(43);
+    expect(Timestamp.lt(timestamp1, timestamp2)).toStrictEqual(true);
+    expect(Timestamp.lt(timestamp2, timestamp1)).toStrictEqual(false);
+    expect(Timestamp.lt(timestamp1, timestamp1)).toStrictEqual(false);
+    expect(Timestamp.gt(timestamp2, timestamp1)).toStrictEqual(true);
+    expect(Timestamp.gt(timestamp1, timestamp2)).toStrictEqual(false);
+    expect(Timestamp.gt(timestamp1, timestamp1)).toStrictEqual(false);
+});
+
 test("add()", () => {
     const timestamp1 = Timestamp.fromBeatUnits(42);
     const timestamp2 = Timestamp.fromBeatUnits(43);
@@ -47,6 +70,126 @@ test("mul()", () => {
     expect(() => Timestamp.mul(timestamp, -2)).toThrow();
 });
 
+test("div()", () => {
+    const timestamp1 = Timestamp.fromBeatUnits(43);
+    const timestamp2 = Timestamp.fromBeatUnits(10);
+    expect(Timestamp.div(timestamp1, timestamp2)).toStrictEqual(4);
+    expect(() => Timestamp.div(timestamp1, Timestamp.zero())).toThrow();
+});
+test("mod()", () => {
+    const timestamp1 = Timestamp.fromBeatUnits(43);
+    const timestamp2 = Timestamp.fromBeatUnits(10);
+    expect(Timestamp.mod(timestamp1, timestamp2).getBeatUnits()).toStrictEqual(
+        3,
+    );
+    expect(() => Timestamp.mod(timestamp1, Timestamp.zero())).toThrow();
+});
+
+test("snap()", () => {
+    const step = Timestamp.fromBeatUnits(10);
+    const snap = (beatUnits: number, mode?: SnapMode) =>
+        Timestamp.snap(
+            Timestamp.fromBeatUnits(beatUnits),
+            step,
+            mode,
+        ).getBeatUnits();
+
+    expect(snap(43)).toStrictEqual(40);
+    expect(snap(45)).toStrictEqual(50);
+    expect(snap(47, SnapMode.Floor)).toStrictEqual(40);
+    expect(snap(43, SnapMode.Ceil)).toStrictEqual(50);
+    expect(snap(40, SnapMode.Ceil)).toStrictEqual(40);
+    expect(() => Timestamp.snap(step, Timestamp.zero())).toThrow();
+});
+test("snap() with swing", () => {
+    const step = Timestamp.fromBeatUnits(12);
+    const snap = (beatUnits: number, mode: SnapMode) =>
+        Timestamp.snap(
+            Timestamp.fromBeatUnits(beatUnits),
+            step,
+            mode,
+            1 / 3,
+        ).getBeatUnits();
+
+    expect(snap(15, SnapMode.Floor)).toStrictEqual(0);
+    expect(snap(15, SnapMode.Ceil)).toStrictEqual(16);
+    expect(snap(17, SnapMode.Floor)).toStrictEqual(16);
+    expect(snap(30, SnapMode.Nearest)).toStrictEqual(24);
+});
+test("snap() past infinity", () => {
+    const step = Timestamp.fromBeatUnits(1000);
+    expect(() =>
+        Timestamp.snap(Timestamp.infinity(), step, SnapMode.Ceil),
+    ).toThrow(RangeError);
+});
+
+test("grid()", () => {
+    const grid = Timestamp.grid(
+        Timestamp.fromBeatUnits(5),
+        Timestamp.fromBeatUnits(40),
+        Timestamp.fromBeatUnits(10),
+    );
+    expect(grid.map(ts => ts.getBeatUnits())).toStrictEqual([10, 20, 30]);
+});
+test("grid() with swing", () => {
+    const grid = Timestamp.grid(
+        Timestamp.zero(),
+        Timestamp.fromBeatUnits(48),
+        Timestamp.fromBeatUnits(12),
+        1 / 3,
+    );
+    expect(grid.map(ts => ts.getBeatUnits())).toStrictEqual([0, 16, 24, 40]);
+    expect(() =>
+        Timestamp.grid(
+            Timestamp.zero(),
+            Timestamp.fromBeatUnits(48),
+            Timestamp.fromBeatUnits(12),
+            1,
+        ),
+    ).toThrow(RangeError);
+});
+test("grid() with too many lines throws", () => {
+    expect(() =>
+        Timestamp.grid(
+            Timestamp.zero(),
+            Timestamp.infinity(),
+            Timestamp.fromBeatUnits(1),
+        ),
+    ).toThrow(RangeError);
+    expect(
+        Timestamp.grid(
+            Timestamp.zero(),
+            Timestamp.fromBeatUnits(2 ** 16),
+            Timestamp.fromBeatUnits(1),
+        ).length,
+    ).toStrictEqual(2 ** 16);
+});
+
+test("toJSON() and fromJSON()", () => {
+    const timestamp = Timestamp.fromBeatUnits(4242);
+    const json = JSON.parse(JSON.stringify({ timestamp }));
+    expect(json).toStrictEqual({ timestamp: { beatUnits: 4242 } });
+    expect(Timestamp.fromJSON(json.timestamp).getBeatUnits()).toStrictEqual(
+        4242,
+    );
+});
+test("fromJSON() rejects invalid values", () => {
+    expect(() => Timestamp.fromJSON({ beatUnits: -1 })).toThrow(RangeError);
+    expect(() => Timestamp.fromJSON({ beatUnits: 1.5 })).toThrow(RangeError);
+    expect(() => Timestamp.fromJSON({ beatUnits: 2 ** 32 })).toThrow(
+        RangeError,
+    );
+});
+test("toString()", () => {
+    expect(String(Timestamp.fromBeatUnits(42))).toContain("42");
+});
+test("valueOf()", () => {
+    const timestamp1 = Timestamp.fromBeatUnits(42);
+    const timestamp2 = Timestamp.fromBeatUnits(43);
+    expect(+timestamp1).toStrictEqual(42);
+    expect(+timestamp1 < +timestamp2).toStrictEqual(true);
+});
+
 test("zero() is zero", () => {
     expect(Timestamp.zero().getBeatUnits()).toStrictEqual(0);
 });
@@ -64,12 +207,44 @@ test("Beats -> Beats", () => {
     const timestamp = Timestamp.fromBeats(original);
     expect(timestamp.getBeats()).toStrictEqual(original);
 });
+test("Fractional beats -> Beat units", () => {
+    expect(Timestamp.fromBeats(1.5).getBeatUnits()).toStrictEqual(1536);
+    // Rounded to the nearest beat unit
+    expect(Timestamp.fromBeats(1 / 3).getBeatUnits()).toStrictEqual(341);
+    expect(Timestamp.fromBeats(2 / 3).getBeatUnits()).toStrictEqual(683);
+});
+test("fromBeats() rejects out of range values", () => {
+    expect(() => Timestamp.fromBeats(-1)).toThrow(RangeError);
+    expect(() => Timestamp.fromBeats(2 ** 22)).toThrow(RangeError);
+});
+test("Seconds -> Seconds", () => {
+    const original = 1.5;
+    const timestamp = Timestamp.fromSeconds(original, 120);
+    expect(timestamp.toSeconds(120)).toStrictEqual(original);
+});
+test("Seconds -> Beat units", () => {
+    // 1.5 seconds at 120 BPM is 3 beats
+    const timestamp = Timestamp.fromSeconds(1.5, 120);
+    expect(timestamp.getBeatUnits()).toStrictEqual(3 * 1024);
+});
+test("fromSeconds() rejects out of range values", () => {
+    expect(() => Timestamp.fromSeconds(-1, 120)).toThrow(RangeError);
+    expect(() => Timestamp.fromSeconds(1, 0)).toThrow(RangeError);
+    expect(() => Timestamp.fromSeconds(1, -120)).toThrow(RangeError);
+    expect(() => Timestamp.fromSeconds(2 ** 32, 120)).toThrow(RangeError);
+});
 test("Samples -> Samples", () => {
     // The number 375 fits nicely into the roundings of the conversion
     const original = 375;
     const timestamp = Timestamp.fromSamples(original, 48_000, 120);
     expect(timestamp.getSamples(48_000, 120)).toStrictEqual(original);
 });
+test("getSamples() and fromSamples() reject out of range BPM", () => {
+    const timestamp = Timestamp.fromBeats(1);
+    expect(() => timestamp.getSamples(48_000, -1)).toThrow(RangeError);
+    expect(() => timestamp.getSamples(48_000, 2 ** 16)).toThrow(RangeError);
+    expect(() => Timestamp.fromSamples(1, 48_000, -1)).toThrow(RangeError);
+});
 test("Beats -> Beat units", () => {
     const original = 42;
     const timestamp = Timestamp.fromBeats(original);